@@ -0,0 +1,224 @@
+//! Inspect traces recorded by the FFX auto splitter.
+//!
+//! Traces are recorded with the `Record a trace` setting and end up in the
+//! LiveSplit log. Every command accepts the whole log, other lines are ignored.
+
+use ffx_autosplitter::{
+    Settings, lss,
+    replay::{self, Trace},
+};
+use std::{env, fmt, fs, process::ExitCode};
+
+const USAGE: &str = "\
+Usage:
+    ffx-trace timeline <log>
+        Print level transitions, story progression changes and battles.
+
+    ffx-trace diff <log-a> <log-b>
+        Compare the timelines and the splits of two traces.
+
+    ffx-trace splits <log> [options]
+        Replay the trace and print every start, split, reset and load removal.
+
+        --all                  Enable all splits
+        --none                 Disable all splits
+        --enable <Split,..>    Enable the given splits, e.g. `Lagoon,MrrSkip`
+        --disable <Split,..>   Disable the given splits
+        --set <option>=<bool>  Set a general option, e.g. `reset=true`
+
+        Options are applied in order, on top of the default settings.
+
+    ffx-trace lss <splits-file>
+        Print the split that every segment of a LiveSplit splits file is matched to.
+";
+
+/// The auto splitter runs at 30 ticks per second.
+const TICK_RATE: u64 = 30;
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let result = match args.as_slice() {
+        ["timeline", log] => timeline(log),
+        ["diff", a, b] => diff(a, b),
+        ["splits", log, options @ ..] => splits(log, options),
+        ["lss", file] => segments(file),
+        _ => Err(String::from(USAGE)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn load(path: &str) -> Result<Trace, String> {
+    let log = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let trace = log.parse::<Trace>().map_err(|e| format!("{path}: {e}"))?;
+    if trace.sections.is_empty() {
+        return Err(format!("{path}: no trace found"));
+    }
+    return Ok(trace);
+}
+
+fn timeline(path: &str) -> Result<(), String> {
+    let trace = load(path)?;
+    for entry in replay::timeline(&trace) {
+        println!("{} {}", Time(entry.tick), entry.change);
+    }
+    return Ok(());
+}
+
+fn splits(path: &str, options: &[&str]) -> Result<(), String> {
+    let trace = load(path)?;
+    let settings = parse_settings(options)?;
+    for event in replay::replay(&settings, &trace) {
+        println!("{} {}", Time(event.tick), event.kind);
+    }
+    return Ok(());
+}
+
+fn segments(path: &str) -> Result<(), String> {
+    let lss = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    for segment in lss::segment_names(&lss) {
+        match lss::find_split(segment) {
+            Some(split) => println!("{segment} => {split:?}"),
+            None => println!("{segment} => no matching split"),
+        }
+    }
+    return Ok(());
+}
+
+fn parse_settings(options: &[&str]) -> Result<Settings, String> {
+    let mut settings = Settings::defaults();
+    let mut options = options.iter();
+
+    while let Some(&option) = options.next() {
+        let mut value = || {
+            options
+                .next()
+                .copied()
+                .ok_or_else(|| format!("missing value for {option}"))
+        };
+        match option {
+            "--all" | "--none" => {
+                for split in replay::all_splits() {
+                    settings.set_split(split, option == "--all");
+                }
+            }
+            "--enable" | "--disable" => {
+                for name in value()?.split(',') {
+                    let split = replay::split_by_name(name.trim())
+                        .ok_or_else(|| format!("unknown split `{name}`"))?;
+                    settings.set_split(split, option == "--enable");
+                }
+            }
+            "--set" => {
+                let value = value()?;
+                let (key, enabled) = value
+                    .split_once('=')
+                    .and_then(|(key, enabled)| Some((key, enabled.parse::<bool>().ok()?)))
+                    .ok_or_else(|| format!("invalid option `{value}`"))?;
+                if settings.set_option(key, enabled) == false {
+                    return Err(format!("unknown option `{key}`"));
+                }
+            }
+            otherwise => return Err(format!("unknown argument `{otherwise}`\n\n{USAGE}")),
+        }
+    }
+
+    return Ok(settings);
+}
+
+fn diff(a: &str, b: &str) -> Result<(), String> {
+    let trace_a = load(a)?;
+    let trace_b = load(b)?;
+
+    println!("--- {a}");
+    println!("+++ {b}");
+
+    println!("\n@@ timeline @@");
+    let timeline_a = replay::timeline(&trace_a);
+    let timeline_b = replay::timeline(&trace_b);
+    print_diff(
+        &timeline_a,
+        &timeline_b,
+        |entry| entry.change,
+        |entry| (entry.tick, entry.change.to_string()),
+    );
+
+    println!("\n@@ splits (default settings) @@");
+    let settings = Settings::defaults();
+    let events_a = replay::replay(&settings, &trace_a);
+    let events_b = replay::replay(&settings, &trace_b);
+    print_diff(
+        &events_a,
+        &events_b,
+        |event| event.kind,
+        |event| (event.tick, event.kind.to_string()),
+    );
+
+    return Ok(());
+}
+
+/// Print a line based diff of `a` and `b`, comparing the items by `key`.
+fn print_diff<T, K: PartialEq>(
+    a: &[T],
+    b: &[T],
+    key: impl Fn(&T) -> K,
+    line: impl Fn(&T) -> (u64, String),
+) {
+    let a_keys = a.iter().map(&key).collect::<Vec<_>>();
+    let b_keys = b.iter().map(&key).collect::<Vec<_>>();
+
+    // longest common subsequence, lengths[i][j] is the LCS of a[i..] and b[j..]
+    let width = b.len() + 1;
+    let mut lengths = vec![0_u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i * width + j] = if a_keys[i] == b_keys[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let print = |sign: char, item: &T| {
+        let (tick, text) = line(item);
+        println!("{sign} {} {text}", Time(tick));
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a_keys[i] == b_keys[j] {
+            i += 1;
+            j += 1;
+        } else if j == b.len()
+            || (i < a.len() && lengths[(i + 1) * width + j] >= lengths[i * width + j + 1])
+        {
+            print('-', &a[i]);
+            i += 1;
+        } else {
+            print('+', &b[j]);
+            j += 1;
+        }
+    }
+}
+
+/// Formats a tick as the real time since the start of the trace.
+struct Time(u64);
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = self.0 * 1000 / TICK_RATE;
+        let (secs, millis) = (millis / 1000, millis % 1000);
+        let (mins, secs) = (secs / 60, secs % 60);
+        let (hours, mins) = (mins / 60, mins % 60);
+        write!(f, "{hours}:{mins:02}:{secs:02}.{millis:03} [{:>7}]", self.0)
+    }
+}
@@ -0,0 +1,198 @@
+//! Names of the encounters, by their map and formation.
+//!
+//! The name of the current encounter is published as the `battle` variable
+//! while a battle is ongoing. When it ends, it moves to `last_battle`.
+//! Only boss battles have a name, there is no table of the random encounters.
+//! The bosses below are the ones that are told apart by their encounter, any
+//! other battle that a split is waiting for is named after that split. Every
+//! other encounter is shown by its map and formation.
+//!
+//! During a battle, the HP of the enemy slots A to D are published as
+//! `enemy_a_hp` to `enemy_d_hp`, and cleared again when it ends. Only those
+//! four slots have known offsets, and there is none for the max HP yet.
+//! A slot that could not be read is not published.
+//!
+//! Every battle of a run is timed by the in-game time. The duration of the
+//! last battle, of all battles and of the boss battles are published as
+//! `last_battle_time`, `total_battle_time` and `boss_battle_time`.
+//! A boss battle is any battle that a split is waiting for.
+
+use crate::{
+    Formation, GameMemory, Hp, Read, Splits, log, split_table, timer_control::TimerControl,
+};
+
+/// The boss battles that are told apart by their encounter,
+/// as `(map id, formation, name)`.
+const BOSS_BATTLES: &[(u16, Formation, &str)] = &[
+    (4, Formation::new(0, 0), "Tros"),
+    (12, Formation::new(0, 0), "First Workers"),
+    (13, Formation::new(0, 0), "Second Workers"),
+    (14, Formation::new(0, 0), "Third Workers"),
+    (17, Formation::new(0, 1), "Garuda"),
+    (44, Formation::new(0, 1), "Wendigo"),
+    (48, Formation::new(2, 0), "Sandragora"),
+    (53, Formation::new(0, 0), "Bevelle Guards #1"),
+    (53, Formation::new(0, 1), "Bevelle Guards #2"),
+    (53, Formation::new(0, 2), "Bevelle Guards #3"),
+    (54, Formation::new(2, 2), "Isaaru"),
+    (55, Formation::new(1, 0), "Evrae Altana"),
+    (61, Formation::new(0, 0), "Defender"),
+    (65, Formation::new(0, 0), "Seymour Flux"),
+    (68, Formation::new(0, 0), "Sanctuary Keeper"),
+    (78, Formation::new(0, 0), "Seymour Omnis"),
+    (87, Formation::new(0, 0), "Home Bombs"),
+    (87, Formation::new(0, 2), "Home Dual Horns"),
+    (87, Formation::new(0, 3), "Home Chimeras"),
+];
+
+fn name(map_id: u16, formation: Formation) -> Option<&'static str> {
+    return BOSS_BATTLES
+        .iter()
+        .find(|(map, f, _)| *map == map_id && *f == formation)
+        .map(|(_, _, name)| *name);
+}
+
+/// The name of the current encounter, or its map and formation if it has none.
+fn current_battle(read: &mut Read<'_, impl GameMemory>) -> String {
+    let map_id = read.map_id().current;
+    let formation = read.formation_id().current;
+    let name =
+        name(map_id, formation).or_else(|| split_table::boss_battle(read).map(Splits::description));
+    return match name {
+        Some(name) => String::from(name),
+        None => {
+            let [id1, id2] = formation.0.to_ne_bytes();
+            format!("Map {map_id} ({id1}, {id2})")
+        }
+    };
+}
+
+/// Publish the current encounter when a battle starts or ends.
+pub fn update(read: &mut Read<'_, impl GameMemory>, control: &mut impl TimerControl) {
+    let battle_state = *read.battle_state();
+    let started = battle_state.current.in_battle();
+    if started == battle_state.old.in_battle() {
+        return;
+    }
+
+    let battle = current_battle(read);
+
+    if started {
+        control.set_variable("battle", &battle);
+    } else {
+        control.set_variable("battle", "");
+        control.set_variable("last_battle", &battle);
+    }
+}
+
+const ENEMY_HP: [&str; 4] = ["enemy_a_hp", "enemy_b_hp", "enemy_c_hp", "enemy_d_hp"];
+
+/// Publish the HP of every enemy slot during a battle, and clear it afterwards.
+pub fn update_enemy_hp(read: &mut Read<'_, impl GameMemory>, control: &mut impl TimerControl) {
+    let battle_state = *read.battle_state();
+    if battle_state.current.in_battle() == false {
+        if battle_state.old.in_battle() {
+            for key in ENEMY_HP {
+                control.set_variable(key, "");
+            }
+        }
+        return;
+    }
+    let started = battle_state.old.in_battle() == false;
+
+    for (hp, key) in read.hp_enemies().iter().zip(ENEMY_HP) {
+        if hp.current == Hp::UNKNOWN {
+            continue;
+        }
+        if started || hp.changed() {
+            control.set_variable_int(key, hp.current);
+        }
+    }
+}
+
+struct TimedBattle {
+    name: String,
+    seconds: u32,
+    boss: bool,
+}
+
+/// The durations of the battles in the current run.
+pub struct BattleTimes {
+    started_at: Option<u32>,
+    total: u32,
+    bosses: u32,
+    battles: Vec<TimedBattle>,
+}
+
+impl BattleTimes {
+    pub const fn new() -> Self {
+        Self {
+            started_at: None,
+            total: 0,
+            bosses: 0,
+            battles: Vec::new(),
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        read: &mut Read<'_, impl GameMemory>,
+        control: &mut impl TimerControl,
+    ) {
+        let battle_state = *read.battle_state();
+        if battle_state.current.in_battle() == battle_state.old.in_battle() {
+            return;
+        }
+        let igt = read.igt().current;
+        if battle_state.current.in_battle() {
+            self.started_at = Some(igt);
+            return;
+        }
+        let Some(started_at) = self.started_at.take() else {
+            return;
+        };
+
+        let seconds = igt.saturating_sub(started_at);
+        let boss = split_table::is_boss_battle(read);
+        self.total += seconds;
+        if boss {
+            self.bosses += seconds;
+        }
+        self.battles.push(TimedBattle {
+            name: current_battle(read),
+            seconds,
+            boss,
+        });
+
+        control.set_variable("last_battle_time", &format_time(seconds));
+        control.set_variable("total_battle_time", &format_time(self.total));
+        control.set_variable("boss_battle_time", &format_time(self.bosses));
+    }
+
+    /// The number of battles in the run so far.
+    pub fn count(&self) -> usize {
+        return self.battles.len();
+    }
+
+    /// Log the duration of every battle, when the run is over.
+    pub fn finish(&self) {
+        if self.battles.is_empty() {
+            return;
+        }
+        log!("Battles:");
+        for battle in &self.battles {
+            let boss = if battle.boss { " (boss)" } else { "" };
+            log!("{:>8} {}{}", format_time(battle.seconds), battle.name, boss);
+        }
+        log!("{:>8} in all battles", format_time(self.total));
+        log!("{:>8} in boss battles", format_time(self.bosses));
+    }
+}
+
+fn format_time(seconds: u32) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        return format!("{hours}:{minutes:02}:{seconds:02}");
+    }
+    return format!("{minutes}:{seconds:02}");
+}
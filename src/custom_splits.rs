@@ -0,0 +1,480 @@
+//! Custom splits that are configured in the settings.
+//!
+//! The settings GUI only offers toggles and dropdowns, so a custom split is
+//! made from a few dropdowns: what kind of event to split on, and the levels
+//! or the story progression that the event has to match.
+//!
+//! The dropdowns only offer the levels and story progressions that are known
+//! to the splitter. For anything else, a custom split can instead use a rule
+//! from the custom splits file, a text file with one rule per line:
+//!
+//! ```text
+//! # comments start with a `#`
+//! Custom1 = level 18 -> 46
+//! Custom2 = level any -> 46 story 330
+//! Custom3 = battle 1540
+//! Custom4 = battle 2530 map 65 formation 0/0 fanfare
+//! Custom5 = story 2767 -> 2775
+//! ```
+//!
+//! - `level <from> -> <to>` moves from one level to another, by their ids.
+//!   Either can be `any`, `story <n>` only splits at that story progression.
+//! - `battle <story>` is a battle that ends at the story progression.
+//!   `map <id> formation <id1>/<id2>` only splits for that encounter, as shown
+//!   in the `battle` variable. `fanfare` requires the victory fanfare, and
+//!   `escape` also splits when the battle was escaped from.
+//! - `story <from>` is the story progressing past a value, `-> <to>` only
+//!   splits when it goes to that value.
+
+use asr::settings::Gui;
+
+use crate::{
+    Level, Progress, Settings, Splits, log,
+    split_table::{self, Trigger},
+};
+
+/// A dropdown of the known values for a custom split.
+/// A `named enum` can also find the label of a value, outside of WebAssembly.
+macro_rules! choice {
+    (
+        $(#[doc = $doc:tt])*
+        enum $name:ident: $owner:ident {
+            #[doc = $none_label:tt]
+            $none:ident,
+            $(
+                #[doc = $label:tt]
+                $variant:ident = $value:ident,
+            )*
+        }
+    ) => {
+        $(#[doc = $doc])*
+        #[derive(Gui, Copy, Clone, Debug, Default, PartialEq, Eq)]
+        pub enum $name {
+            #[doc = $none_label]
+            #[default]
+            $none,
+            $(
+                #[doc = $label]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            pub const fn value(self) -> Option<u32> {
+                return match self {
+                    Self::$none => None,
+                    $(Self::$variant => Some($owner::$value),)*
+                };
+            }
+        }
+    };
+    (
+        $(#[doc = $doc:tt])*
+        named enum $name:ident: $owner:ident {
+            #[doc = $none_label:tt]
+            $none:ident,
+            $(
+                #[doc = $label:tt]
+                $variant:ident = $value:ident,
+            )*
+        }
+    ) => {
+        choice! {
+            $(#[doc = $doc])*
+            enum $name: $owner {
+                #[doc = $none_label]
+                $none,
+                $(
+                    #[doc = $label]
+                    $variant = $value,
+                )*
+            }
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        impl $name {
+            const ALL: &[Self] = &[$(Self::$variant),*];
+
+            pub fn name(self) -> &'static str {
+                return match self {
+                    Self::$none => $none_label.trim_start(),
+                    $(Self::$variant => $label.trim_start(),)*
+                };
+            }
+
+            pub fn find(value: u32) -> Option<Self> {
+                return Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|choice| choice.value() == Some(value));
+            }
+        }
+    };
+}
+
+/// What kind of event a custom split is looking for.
+#[derive(Gui, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CustomKind {
+    /// Disabled
+    #[default]
+    Off,
+    /// Moving from one level to another
+    LevelTransition,
+    /// A battle ends at the story progression
+    BattleEnd,
+    /// The story progresses past the story progression
+    StoryAdvance,
+    /// The rule from the custom splits file
+    FromFile,
+}
+
+choice! {
+    /// A level for a custom split, sorted by name.
+    enum LevelChoice: Level {
+        /// Any level
+        Any,
+        /// Besaid Beach
+        BesaidBeach = BESAID_BEACH,
+        /// Besaid End Road
+        BesaidEndRoad = BESAID_END_ROAD,
+        /// Besaid Promontory
+        BesaidPromontory = BESAID_PROMONTORY,
+        /// Besaid Village
+        BesaidVillage = BESAID_VILLAGE,
+        /// Besaid Village Road
+        BesaidVillageRoad = BESAID_VILLAGE_ROAD,
+        /// Bevelle Antechamber
+        BevelleAntechamber = BEVELLE_ANTECHAMBER,
+        /// Bevelle Trials
+        BevelleTrials = BEVELLE_TRIALS,
+        /// Bikanel North
+        BikanelNorth = BIKANEL_NORTH,
+        /// Calm Lands
+        CalmLands = CALM_LANDS,
+        /// Calm Lands Bridge
+        CalmLandsBridge = CALM_LANDS_BRIDGE,
+        /// Crevasse
+        Crevasse = CREVASSE,
+        /// Djose Fayth
+        DjoseFayth = DJOSE_FAYTH,
+        /// Djose Highroad
+        DjoseHighroad = DJOSE_HIGHROAD,
+        /// Djose Outside
+        DjoseOutside = DJOSE_OUTSIDE,
+        /// Djose Pilgrimage Road
+        DjosePilgrimageRoad = DJOSE_PILGRIMAGE_ROAD,
+        /// Dream's End
+        DreamsEnd = DREAMS_END,
+        /// Guadosalam
+        Guadosalam = GUADOSALAM,
+        /// Highbridge
+        Highbridge = HIGHBRIDGE,
+        /// Highroad Agency
+        HighroadAgency = HIGHROAD_AGENCY,
+        /// Highroad Central
+        HighroadCentral = HIGHROAD_CENTRAL,
+        /// Highroad North End
+        HighroadNorthEnd = HIGHROAD_NORTH_END,
+        /// Home Entrance
+        HomeEntrance = HOME_ENTRANCE,
+        /// Home Environment Controls
+        HomeEnvironmentControls = HOME_ENVIRONMENT_CONTROLS,
+        /// Home Main Corridor
+        HomeMainCorridor = HOME_MAIN_CORRIDOR,
+        /// Kilika Fayth
+        KilikaFayth = KILIKA_FAYTH,
+        /// Kilika Residential Area
+        KilikaResidentialArea = KILIKA_RESIDENTIAL_AREA,
+        /// Kilika Temple
+        KilikaTemple = KILIKA_TEMPLE,
+        /// Kilika Woods
+        KilikaWoods = KILIKA_WOODS,
+        /// Lagoon
+        Lagoon = LAGOON,
+        /// Luca Dock 1
+        LucaDock1 = LUCA_DOCK_1,
+        /// Luca Dock 5
+        LucaDock5 = LUCA_DOCK_5,
+        /// Luca Main
+        LucaMain = LUCA_MAIN,
+        /// Macalania Antechamber
+        MacalaniaAntechamber = MACALNIA_ANTECHAMBER,
+        /// Macalania Hallway
+        MacalaniaHallway = MACALNIA_HALLWAY,
+        /// Macalania Lake
+        MacalaniaLake = MACALNIA_LAKE,
+        /// Macalania Lake Shop
+        MacalaniaLakeShop = MACALNIA_LAKE_SHOP,
+        /// Macalania Spherimorph
+        MacalaniaSpherimorph = MACALANIA_SPHERIMORPH,
+        /// Macalania Spring
+        MacalaniaSpring = MACALANIA_SPRING,
+        /// Macalania Temple
+        MacalaniaTemple = MACALANIA_TEMPLE,
+        /// Macalania Temple Road
+        MacalaniaTempleRoad = MACALANIA_TEMPLE_ROAD,
+        /// Macalania Woods North
+        MacalaniaWoodsNorth = MACALANIA_WOODS_NORTH,
+        /// Macalania Woods South
+        MacalaniaWoodsSouth = MACALANIA_WOODS_SOUTH,
+        /// Moonflow North
+        MoonflowNorth = MOONFLOW_NORTH,
+        /// Moonflow South Bank
+        MoonflowSouthBank = MOONFLOW_SOUTH_BANK,
+        /// Moonflow South Bank Road
+        MoonflowSouthBankRoad = MOONFLOW_SOUTH_BANK_ROAD,
+        /// Mushroom Rock Aftermath
+        MushroomRockAftermath = MUSHROOM_ROCK_AFTERMATH,
+        /// Mushroom Rock Road
+        MushroomRockRoad = MUSHROOM_ROCK_ROAD,
+        /// New Game
+        NewGame = NEW_GAME,
+        /// Nucleus
+        Nucleus = NUCLEUS,
+        /// Stadium Pool
+        StadiumPool = STADIUM_POOL,
+        /// Stadium Stands
+        StadiumStands = STADIUM_STANDS,
+        /// Thunder Plains North
+        ThunderPlainsNorth = THUNDERPLAINS_NORTH,
+        /// Thunder Plains South
+        ThunderPlainsSouth = THUNDERPLAINS_SOUTH,
+        /// Via Underwater
+        ViaUnderwater = VIA_UNDERWATER,
+        /// Zanarkand Dome
+        ZanarkandDome = ZANARKAND_DOME,
+        /// Zanarkand Road
+        ZanarkandRoad = ZANARKAND_ROAD,
+    }
+}
+
+choice! {
+    /// A story progression for a custom split, in story order.
+    named enum StoryChoice: Progress {
+        /// Not selected
+        Unset,
+        /// Sinspawn Ammes
+        Ammes = AMMES,
+        /// Klikk
+        Klikk = KLIKK,
+        /// Tros
+        Tros = TROS,
+        /// Piranhas
+        Lagoon = LAGOON,
+        /// Kimahri
+        Kimahri = KIMAHRI,
+        /// Sin's Fin
+        Sinfin = SINFIN,
+        /// Sinspawn Echuilles
+        Echuilles = ECHUILLES,
+        /// Sinspawn Geneaux
+        Geneaux = GENEAUX,
+        /// Machina Workers
+        Workers = WORKERS,
+        /// Oblitzerator
+        Oblitzerator = OBLITZERATOR,
+        /// Blitzball
+        Blitzball = BLITZBALL,
+        /// Sahagin Chiefs
+        Sahagins = SAHAGINS,
+        /// Garuda
+        Garuda = GARUDA,
+        /// Chocobo Eater
+        ChocoboEater = CHOCOBO_EATER,
+        /// Mushroom Rock Road
+        Mrr = MRR,
+        /// Sinspawn Gui
+        Gui = GUI,
+        /// Extractor
+        Extractor = EXTRACTOR,
+        /// Spherimorph
+        Spherimorph = SPHERIMORPH,
+        /// Crawler
+        Crawler = CRAWLER,
+        /// Seymour
+        Seymour = SEYMOUR,
+        /// Wendigo
+        Wendigo = WENDIGO,
+        /// Bikanel, Kimahri joined
+        BikanelKimahri = BIKANEL_KIMAHRI,
+        /// Bikanel, Rikku joined
+        BikanelRikku = BIKANEL_RIKKU,
+        /// Home
+        Home = HOME,
+        /// Home Chimeras
+        Home2 = HOME2,
+        /// Evrae
+        Evrae = EVRAE,
+        /// Bevelle Guards
+        Guards = GUARDS,
+        /// Isaaru
+        Isaaru = ISAARU,
+        /// Seymour Natus
+        Natus = NATUS,
+        /// Defender X
+        Defender = DEFENDER,
+        /// Biran & Yenke
+        BiranYenke = BIRAN_YENKE,
+        /// Seymour Flux
+        Flux = FLUX,
+        /// Sanctuary Keeper
+        SanctuaryKeeper = SANCTUARY_KEEPER,
+        /// Zanarkand Trials
+        Tetris = TETRIS,
+        /// Spectral Keeper
+        SpectralKeeper = SPECTRAL_KEEPER,
+        /// Yunalesca
+        Yunalesca = YUNALESCA,
+        /// Sin Core
+        SinCore = SIN_CORE,
+        /// Overdrive Sin
+        OverdriveSin = OVERDRIVE_SIN,
+        /// Seymour Omnis
+        Omnis = OMNIS,
+        /// Braska's Final Aeon
+        Bfa = BFA,
+        /// Yu Yevon
+        YuYevon = YU_YEVON,
+    }
+}
+
+/// The trigger of a custom split, if it is enabled and complete.
+pub fn trigger(
+    kind: CustomKind,
+    from: LevelChoice,
+    to: LevelChoice,
+    story: StoryChoice,
+    rule: Option<Trigger>,
+) -> Option<Trigger> {
+    return match kind {
+        CustomKind::Off => None,
+        CustomKind::LevelTransition => Some(Trigger::Level {
+            from: from.value(),
+            to: to.value(),
+            story: None,
+        }),
+        CustomKind::BattleEnd => Some(split_table::battle(story.value()?)),
+        CustomKind::StoryAdvance => Some(split_table::advance(story.value()?)),
+        CustomKind::FromFile => rule,
+    };
+}
+
+/// The rules from the custom splits file.
+pub struct Rules {
+    path: String,
+    rules: Vec<(Splits, Trigger)>,
+}
+
+impl Rules {
+    pub const fn new() -> Self {
+        Self {
+            path: String::new(),
+            rules: Vec::new(),
+        }
+    }
+
+    /// Read the rules again when another file is selected.
+    pub fn update(&mut self, settings: &Settings) {
+        if *settings.custom_splits_file.path == *self.path {
+            return;
+        }
+        self.path = settings.custom_splits_file.path.to_string();
+        self.rules = load_rules(&self.path);
+    }
+
+    /// The rule for the custom split.
+    pub fn get(&self, split: Splits) -> Option<Trigger> {
+        return self
+            .rules
+            .iter()
+            .find_map(|&(s, rule)| (s == split).then_some(rule));
+    }
+}
+
+fn load_rules(path: &str) -> Vec<(Splits, Trigger)> {
+    if path.is_empty() {
+        return Vec::new();
+    }
+    let rules = match std::fs::read_to_string(path) {
+        Ok(rules) => rules,
+        Err(e) => {
+            log!("Could not read custom splits {}: {}", path, e);
+            return Vec::new();
+        }
+    };
+    let rules = parse_rules(&rules);
+    log!("Loaded {} custom split rules from {}", rules.len(), path);
+    return rules;
+}
+
+/// Parse the rules of the custom splits file, ignoring invalid lines.
+pub fn parse_rules(rules: &str) -> Vec<(Splits, Trigger)> {
+    let mut parsed = Vec::new();
+    for line in rules.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let rule = line.split_once('=').and_then(|(name, rule)| {
+            let name = name.trim();
+            let split = Splits::CUSTOM
+                .iter()
+                .copied()
+                .find(|split| split.name().eq_ignore_ascii_case(name))?;
+            return Some((split, parse_rule(rule)?));
+        });
+        match rule {
+            Some(rule) => parsed.push(rule),
+            None => log!("Ignoring invalid custom split rule: {}", line),
+        }
+    }
+    return parsed;
+}
+
+fn parse_rule(rule: &str) -> Option<Trigger> {
+    let mut words = rule.split_ascii_whitespace();
+    let mut trigger = match words.next()? {
+        "level" => {
+            let from = level(words.next()?)?;
+            if words.next()? != "->" {
+                return None;
+            }
+            let to = level(words.next()?)?;
+            Trigger::Level {
+                from,
+                to,
+                story: None,
+            }
+        }
+        "battle" => split_table::battle(words.next()?.parse().ok()?),
+        "story" => split_table::advance(words.next()?.parse().ok()?),
+        _ => return None,
+    };
+
+    while let Some(word) = words.next() {
+        trigger = match (word, trigger) {
+            ("story", Trigger::Level { .. }) => trigger.story(words.next()?.parse().ok()?),
+            ("->", Trigger::Advance { .. }) => trigger.to(words.next()?.parse().ok()?),
+            ("map", Trigger::Battle { .. }) => {
+                let map_id = words.next()?.parse().ok()?;
+                if words.next()? != "formation" {
+                    return None;
+                }
+                let (id1, id2) = words.next()?.split_once('/')?;
+                trigger.at(map_id, id1.parse().ok()?, id2.parse().ok()?)
+            }
+            ("fanfare", Trigger::Battle { .. }) => trigger.fanfare(),
+            ("escape", Trigger::Battle { .. }) => trigger.escapable(),
+            _ => return None,
+        };
+    }
+
+    return Some(trigger);
+}
+
+/// A level id, or `None` for `any` level.
+fn level(word: &str) -> Option<Option<u32>> {
+    if word.eq_ignore_ascii_case("any") {
+        return Some(None);
+    }
+    return word.parse().ok().map(Some);
+}
@@ -0,0 +1,196 @@
+//! Encounters per split, to see which segment had more random encounters.
+//!
+//! The encounter counter is snapshot on every split. The encounters since the
+//! last split are published as `encounters_this_split`, those of the split
+//! before as `encounters_last_split`. When the run ends, the encounters of
+//! every split are written to the log.
+//!
+//! An encounter route can be selected in the settings, a text file with the
+//! expected encounters of a split on every line, e.g. `Kimahri = 3`. Lines
+//! starting with `#` are ignored. The difference to the route is published as
+//! `encounter_delta`, and as `encounter_route` being `ahead` or `behind`.
+//! The current split counts as soon as it has more encounters than planned.
+//!
+//! When a split is undone, its encounters are taken out of the history again
+//! and count towards the current split.
+
+use strum::IntoEnumIterator as _;
+
+use crate::{GameMemory, Read, Settings, Splits, log, timer_control::TimerControl};
+
+pub struct Encounters {
+    /// The encounter counter at the start of the current split.
+    at_last_split: Option<u32>,
+    current: u32,
+    history: Vec<(Splits, u32)>,
+    route_path: String,
+    route: Vec<(Splits, u32)>,
+    delta: Option<i64>,
+}
+
+impl Encounters {
+    pub const fn new() -> Self {
+        Self {
+            at_last_split: None,
+            current: 0,
+            history: Vec::new(),
+            route_path: String::new(),
+            route: Vec::new(),
+            delta: None,
+        }
+    }
+
+    /// Encounters since the last split.
+    pub fn this_split(&self) -> u32 {
+        return self
+            .current
+            .saturating_sub(self.at_last_split.unwrap_or(self.current));
+    }
+
+    /// Update the counts, `next` is the split that is expected to happen next.
+    pub fn update(
+        &mut self,
+        settings: &Settings,
+        read: &mut Read<'_, impl GameMemory>,
+        next: Option<Splits>,
+        control: &mut impl TimerControl,
+    ) {
+        if settings.count_encounters == false {
+            return;
+        }
+        if *settings.encounter_route.path != *self.route_path {
+            self.route_path = settings.encounter_route.path.to_string();
+            self.route = load_route(&self.route_path);
+            if self.route.is_empty() && self.delta.is_some() {
+                control.set_variable("encounter_delta", "");
+                control.set_variable("encounter_route", "");
+            }
+            self.delta = None;
+        }
+
+        let encounters = read.encounter_count();
+        self.current = encounters.current;
+        if self.at_last_split.is_none() {
+            self.at_last_split = Some(self.current);
+            control.set_variable_int("encounters_this_split", 0);
+        } else if encounters.changed() {
+            control.set_variable_int("encounters_this_split", self.this_split());
+        }
+
+        self.update_delta(next, control);
+    }
+
+    fn target(&self, split: Splits) -> Option<u32> {
+        return self
+            .route
+            .iter()
+            .find_map(|&(s, target)| (s == split).then_some(target));
+    }
+
+    fn update_delta(&mut self, next: Option<Splits>, control: &mut impl TimerControl) {
+        if self.route.is_empty() {
+            return;
+        }
+
+        let done = self
+            .history
+            .iter()
+            .filter_map(|&(split, encounters)| {
+                let target = self.target(split)?;
+                return Some(i64::from(encounters) - i64::from(target));
+            })
+            .sum::<i64>();
+        let current = next
+            .and_then(|split| self.target(split))
+            .map_or(0, |target| self.this_split().saturating_sub(target));
+        let delta = done + i64::from(current);
+
+        if self.delta == Some(delta) {
+            return;
+        }
+        self.delta = Some(delta);
+
+        let route = match delta.signum() {
+            -1 => "ahead",
+            0 => "on route",
+            _ => "behind",
+        };
+        control.set_variable("encounter_delta", &format!("{delta:+}"));
+        control.set_variable("encounter_route", route);
+    }
+
+    pub fn split(&mut self, settings: &Settings, split: Splits, control: &mut impl TimerControl) {
+        if settings.count_encounters == false {
+            return;
+        }
+
+        let encounters = self.this_split();
+        self.history.push((split, encounters));
+        self.at_last_split = Some(self.current);
+        control.set_variable_int("encounters_last_split", encounters);
+        control.set_variable_int("encounters_this_split", 0);
+    }
+
+    /// The split was undone, its encounters count towards the current split again.
+    pub fn undo(&mut self, split: Splits, control: &mut impl TimerControl) {
+        let Some(&(last, encounters)) = self.history.last() else {
+            return;
+        };
+        if last != split {
+            return;
+        }
+        self.history.pop();
+        self.at_last_split = self.at_last_split.map(|at| at.saturating_sub(encounters));
+        let last_split = self.history.last().map_or(0, |&(_, encounters)| encounters);
+        control.set_variable_int("encounters_last_split", last_split);
+        control.set_variable_int("encounters_this_split", self.this_split());
+    }
+
+    /// Log the encounters of every split, when the run is over.
+    pub fn finish(&self) {
+        if self.history.is_empty() {
+            return;
+        }
+        log!("Encounters per split:");
+        for (split, encounters) in &self.history {
+            log!("{:>4} {:?}", encounters, split);
+        }
+        log!("{:>4} after the last split", self.this_split());
+    }
+}
+
+/// Read the expected encounters per split from the route file.
+fn load_route(path: &str) -> Vec<(Splits, u32)> {
+    if path.is_empty() {
+        return Vec::new();
+    }
+    let route = match std::fs::read_to_string(path) {
+        Ok(route) => route,
+        Err(e) => {
+            log!("Could not read encounter route {}: {}", path, e);
+            return Vec::new();
+        }
+    };
+
+    let mut targets = Vec::new();
+    for line in route.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parsed = line.split_once('=').and_then(|(name, target)| {
+            let name = name.trim();
+            let split = Splits::iter().find(|split| split.name().eq_ignore_ascii_case(name))?;
+            return Some((split, target.trim().parse::<u32>().ok()?));
+        });
+        match parsed {
+            Some(target) => targets.push(target),
+            None => log!("Ignoring invalid encounter route line: {}", line),
+        }
+    }
+    log!(
+        "Loaded encounter route {} for {} splits",
+        path,
+        targets.len()
+    );
+    return targets;
+}
@@ -26,6 +26,27 @@ impl<T: EnumSetMember> EnumSet<T> {
         return previous == 0;
     }
 
+    pub fn remove(&mut self, item: &T) -> bool {
+        let Some(ord) = item.ordinal() else {
+            return false;
+        };
+        if ord >= 128 {
+            return false;
+        }
+
+        let mask = 1_u128 << ord;
+        let previous = self.0 & mask;
+        self.0 &= !mask;
+        return previous != 0;
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        return match item.ordinal() {
+            Some(ord) if ord < 128 => self.0 & (1_u128 << ord) != 0,
+            _ => false,
+        };
+    }
+
     pub fn inner(&self) -> u128 {
         return self.0;
     }
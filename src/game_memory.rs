@@ -0,0 +1,170 @@
+use asr::Process;
+use bytemuck::{CheckedBitPattern, checked};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use strum::{EnumIter, IntoEnumIterator as _};
+
+use crate::Memory;
+
+/// Every value the splitter reads from the game.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive, EnumIter)]
+#[repr(u8)]
+pub enum Pointer {
+    Loading,
+    EncounterCount,
+    Level,
+    StoryProgression,
+    BattleState,
+    CutsceneType,
+    MapId,
+    FormationId,
+    YuYevon,
+    HpEnemyA,
+    SelectScreen,
+    CursorPosition,
+    Input,
+    Igt,
+    HpEnemyB,
+    HpEnemyC,
+    HpEnemyD,
+    #[cfg(testing)]
+    LoadingSlot,
+}
+
+impl Pointer {
+    /// Upper bound for the number of pointers, used to size fixed tables.
+    pub const MAX: usize = 32;
+
+    pub fn index(self) -> usize {
+        return usize::from(u8::from(self));
+    }
+
+    /// Size of the value in the game's memory, in bytes.
+    pub const fn size(self) -> usize {
+        match self {
+            Self::MapId | Self::FormationId => 2,
+            #[cfg(testing)]
+            Self::LoadingSlot => 8,
+            _ => 4,
+        }
+    }
+}
+
+/// Source of the game values that the splitter logic operates on.
+///
+/// The splitter only ever reads through this trait, so the logic
+/// can run against a live process or against values from somewhere else.
+pub trait GameMemory {
+    /// Read the current value behind `pointer`.
+    /// Returns `None` if the value is not available (yet).
+    fn read<T: CheckedBitPattern>(&self, pointer: Pointer) -> Option<T>;
+
+    /// Read the raw bytes behind `pointer`, zero-extended to 64 bits.
+    fn read_raw(&self, pointer: Pointer) -> Option<u64> {
+        return match pointer.size() {
+            2 => self.read::<u16>(pointer).map(u64::from),
+            8 => self.read::<u64>(pointer),
+            _ => self.read::<u32>(pointer).map(u64::from),
+        };
+    }
+}
+
+/// Reads from the attached `FFX.exe` process.
+pub(crate) struct ProcessMemory<'a> {
+    process: &'a Process,
+    memory: &'a Memory,
+}
+
+impl<'a> ProcessMemory<'a> {
+    pub(crate) fn new(process: &'a Process, memory: &'a Memory) -> Self {
+        Self { process, memory }
+    }
+}
+
+impl GameMemory for ProcessMemory<'_> {
+    fn read<T: CheckedBitPattern>(&self, pointer: Pointer) -> Option<T> {
+        let process = self.process;
+        let memory = self.memory;
+        let value = match pointer {
+            Pointer::Loading => memory.is_loading.deref(process),
+            Pointer::EncounterCount => memory.encounter_counter.deref(process),
+            Pointer::Level => memory.current_level.deref(process),
+            Pointer::StoryProgression => memory.story_progression.deref(process),
+            Pointer::BattleState => memory.battle_state.deref(process),
+            Pointer::CutsceneType => memory.cutscene_type.deref(process),
+            Pointer::MapId => memory.map_id.deref(process),
+            Pointer::FormationId => memory.formation_id.deref(process),
+            Pointer::YuYevon => memory.yu_yevon.deref(process),
+            Pointer::HpEnemyA => memory.hp_enemy_a.deref(process),
+            Pointer::SelectScreen => memory.select_screen.deref(process),
+            Pointer::CursorPosition => memory.cursor_position.deref(process),
+            Pointer::Input => memory.input.deref(process),
+            #[cfg(testing)]
+            Pointer::LoadingSlot => memory.loading_slot.deref(process),
+            Pointer::HpEnemyB => memory.hp_enemy_b.deref(process),
+            Pointer::HpEnemyC => memory.hp_enemy_c.deref(process),
+            Pointer::HpEnemyD => memory.hp_enemy_d.deref(process),
+            Pointer::Igt => memory.igt.deref(process),
+        };
+        return value.ok();
+    }
+}
+
+/// In-memory stand-in for the game.
+///
+/// Every pointer holds up to 8 raw little-endian bytes that are
+/// reinterpreted as whatever type the splitter asks for.
+/// Pointers that have not been set read as unavailable,
+/// just like an unmapped address in the real process.
+#[derive(Debug, Clone)]
+pub struct FakeMemory {
+    values: [Option<u64>; Pointer::MAX],
+}
+
+impl FakeMemory {
+    pub const fn new() -> Self {
+        Self {
+            values: [None; Pointer::MAX],
+        }
+    }
+
+    pub fn get(&self, pointer: Pointer) -> Option<u64> {
+        return self.values[pointer.index()];
+    }
+
+    pub fn set(&mut self, pointer: Pointer, value: u64) -> &mut Self {
+        self.values[pointer.index()] = Some(value);
+        return self;
+    }
+
+    pub fn unset(&mut self, pointer: Pointer) -> &mut Self {
+        self.values[pointer.index()] = None;
+        return self;
+    }
+
+    /// Read every value from `game` once, to have them all from the same tick.
+    pub fn capture(game: &impl GameMemory) -> Self {
+        let mut memory = Self::new();
+        for pointer in Pointer::iter() {
+            memory.values[pointer.index()] = game.read_raw(pointer);
+        }
+        return memory;
+    }
+}
+
+impl Default for FakeMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameMemory for FakeMemory {
+    fn read<T: CheckedBitPattern>(&self, pointer: Pointer) -> Option<T> {
+        let bytes = self.get(pointer)?.to_le_bytes();
+        let bytes = bytes.get(..size_of::<T>())?;
+        return checked::try_pod_read_unaligned(bytes).ok();
+    }
+
+    fn read_raw(&self, pointer: Pointer) -> Option<u64> {
+        return self.get(pointer);
+    }
+}
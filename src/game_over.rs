@@ -0,0 +1,61 @@
+//! Recognizing a game over, and what to do about it.
+//!
+//! There is no known value in memory for the game over screen. Instead, a
+//! game over is a battle that ended without being won or escaped from,
+//! followed by the return to the title screen. A soft reset during a battle
+//! returns to the title screen before the battle ends, so it is no game over.
+//! Every game over counts towards the `deaths` variable, which is kept for as
+//! long as the splitter runs.
+//!
+//! Battle splits don't check for a win, the lost battle before a game over
+//! splits the same as a won one, unless the split requires the fanfare.
+
+use asr::settings::Gui;
+
+use crate::{GameMemory, Read, log, timer_control::TimerControl};
+
+#[derive(Gui, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GameOverAction {
+    /// Only count the deaths
+    #[default]
+    Nothing,
+    /// Reset the timer
+    Reset,
+    /// Restart the timer, for practicing a single segment
+    Restart,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct GameOver {
+    /// The last battle was lost.
+    lost: bool,
+    deaths: u32,
+}
+
+impl GameOver {
+    /// Returns `true` on the tick that a game over is recognized.
+    pub fn update(
+        &mut self,
+        read: &mut Read<'_, impl GameMemory>,
+        control: &mut impl TimerControl,
+    ) -> bool {
+        let battle_state = *read.battle_state();
+        if battle_state.changed() {
+            let state = battle_state.current;
+            self.lost = state.is_over() && state.escaped() == false && state.fanfare() == false;
+        }
+
+        let level = *read.level();
+        if level.changed() == false {
+            return false;
+        }
+        let game_over = self.lost && level.current.new_game();
+        self.lost = false;
+        if game_over {
+            self.deaths += 1;
+            log!("Game over! Deaths: {}", self.deaths);
+            control.set_variable_int("deaths", self.deaths);
+        }
+        return game_over;
+    }
+}
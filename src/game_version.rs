@@ -0,0 +1,89 @@
+//! The builds of the game that the splitter knows about.
+//!
+//! Every build has its own memory layout. A build is identified by where the
+//! entry point signature is located, relative to the start of the main module.
+//! So far, only the current Steam release is known. Any other build is
+//! reported as `unsupported`, and its entry point is logged. To support it,
+//! add a variant with that entry point and its offsets.
+
+/// The pointer paths to every value, relative to the start of the main module.
+pub struct Offsets {
+    pub is_loading: [u64; 2],
+    pub encounter_counter: [u64; 1],
+    pub current_level: [u64; 1],
+    pub story_progression: [u64; 1],
+    pub battle_state: [u64; 2],
+    pub cutscene_type: [u64; 1],
+    pub map_id: [u64; 1],
+    pub formation_id: [u64; 1],
+    pub yu_yevon: [u64; 1],
+    pub hp_enemy_a: [u64; 2],
+    pub hp_enemy_b: [u64; 2],
+    pub hp_enemy_c: [u64; 2],
+    pub hp_enemy_d: [u64; 2],
+    pub cursor_position: [u64; 1],
+    pub input: [u64; 1],
+    pub select_screen: [u64; 1],
+    pub igt: [u64; 1],
+    #[cfg(testing)]
+    pub loading_slot: [u64; 1],
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameVersion {
+    /// The current Steam release of the HD Remaster.
+    Steam,
+}
+
+impl GameVersion {
+    const ALL: &[Self] = &[Self::Steam];
+
+    /// The version that has its entry point at the given offset.
+    pub fn from_entry_point(offset: u64) -> Option<Self> {
+        return Self::ALL
+            .iter()
+            .copied()
+            .find(|version| version.entry_point() == offset);
+    }
+
+    /// The name as shown in the `game_version` variable.
+    pub const fn name(self) -> &'static str {
+        return match self {
+            Self::Steam => "PC (Steam)",
+        };
+    }
+
+    const fn entry_point(self) -> u64 {
+        return match self {
+            Self::Steam => 0x5493C8,
+        };
+    }
+
+    pub const fn offsets(self) -> &'static Offsets {
+        return match self {
+            Self::Steam => &STEAM,
+        };
+    }
+}
+
+const STEAM: Offsets = Offsets {
+    is_loading: [0x8CC898, 0x123A4],
+    encounter_counter: [0xD307A4],
+    current_level: [0x8CB990],
+    story_progression: [0x84949C],
+    battle_state: [0x390D90, 0x4],
+    cutscene_type: [0xD27C88],
+    map_id: [0xD2C256],
+    formation_id: [0xD2C258],
+    yu_yevon: [0xD2A8E8],
+    hp_enemy_a: [0xD34460, 0x5D0],
+    hp_enemy_b: [0xD34460, 0x1560],
+    hp_enemy_c: [0xD34460, 0x24F0],
+    hp_enemy_d: [0xD34460, 0x3480],
+    cursor_position: [0x1467808],
+    input: [0x8CB170],
+    select_screen: [0xF25B30],
+    igt: [0xD2CB4C],
+    #[cfg(testing)]
+    loading_slot: [0x8E72DC],
+};
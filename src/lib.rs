@@ -1,25 +1,60 @@
-#![no_std]
-
 use asr::{
     Address, Process,
     deep_pointer::DeepPointer,
     future::{next_tick, retry},
-    settings::{Gui, gui::Title as Heading},
+    settings::{
+        Gui,
+        gui::{FileSelect, Title as Heading},
+    },
     signature::Signature,
-    timer::{self, TimerState},
+    time::Duration,
+    timer::TimerState,
     watcher::{Pair, Watcher},
 };
+use battles::BattleTimes;
 use bytemuck::CheckedBitPattern;
 #[cfg(testing)]
 use bytemuck::checked;
 use core::{fmt, iter, ops::ControlFlow};
+use custom_splits::{CustomKind, LevelChoice, StoryChoice};
+use encounters::Encounters;
+use game_memory::{FakeMemory, GameMemory, Pointer, ProcessMemory};
+use game_over::{GameOver, GameOverAction};
+use game_version::GameVersion;
+use loads::Loads;
 use num_enum::IntoPrimitive;
+use presets::{Preset, Presets};
+use split_order::SplitOrder;
+use split_table::{Trigger, advance, battle, cutscene, hp_zero, level};
+use std::time::Instant;
 use strum::{EnumIter, IntoEnumIterator as _};
+use summary::Summary;
+use timer_control::{AsrTimer, TimerControl};
+use undo::Undo;
 
+mod battles;
 mod enum_set;
+pub mod game_memory;
+mod game_over;
+mod game_version;
+mod loads;
+pub mod lss;
+#[cfg(not(target_family = "wasm"))]
+pub mod replay;
+#[macro_use]
+mod split_table;
+mod custom_splits;
+mod encounters;
+mod presets;
+mod split_order;
+mod summary;
+#[cfg(test)]
+mod tests;
+pub mod timer_control;
+mod trace;
+mod undo;
 
 asr::async_main!(stable);
-asr::panic_handler!();
 
 #[macro_export]
 macro_rules! log {
@@ -29,822 +64,349 @@ macro_rules! log {
             &mut buf,
             ::core::format_args!(concat!("[FFX]: ", $format) $($arg)*),
         );
-        ::asr::print_message(&buf);
+        $crate::print_message(&buf);
     }};
 }
 
-#[cfg(testing)]
-const SPLIT_COOLDOWN_SECS: i32 = 5;
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, EnumIter)]
-#[repr(u8)]
-enum Splits {
-    Ammes,
-    Klikk,
-    Tros,
-    Lagoon,
-    BesaidVillage,
-    Kimahri,
-    BesaidRoad,
-    Echuilles,
-    Geneaux,
-    Ifrit,
-    Kilika,
-    Oblitzerator,
-    BeforeBlitzball,
-    BlitzballDone,
-    Garuda,
-    MiihenRoad,
-    ChocoboEater,
-    OldRoad,
-    Mrr,
-    Gui,
-    MrrSkip,
-    DjoseRoad,
-    Ixion,
-    EnterMoonflow,
-    MoonflowSouth,
-    Extractor,
-    Guadosalam,
-    ThunderPlains,
-    MacalaniaWoods,
-    Spherimorph,
-    Crawler,
-    Seymour,
-    Shiva,
-    Wendigo,
-    Bikanel,
-    Home,
-    Evrae,
-    Guards,
-    Bahamut,
-    ViaPurifico,
-    Isaaru,
-    Natus,
-    CalmLands,
-    BiranYenke,
-    Flux,
-    SanctuaryKeeper,
-    Zanarkand,
-    Tetris,
-    SpectralKeeper,
-    Yunalesca,
-    Core,
-    Overdrive,
-    Omnis,
-    Eggs,
-    Bfa,
-    YuYevon,
-    #[cfg(testing)]
-    SinFin,
-    #[cfg(testing)]
-    Luca,
-    #[cfg(testing)]
-    Workers1,
-    #[cfg(testing)]
-    Workers2,
-    #[cfg(testing)]
-    Workers31,
-    #[cfg(testing)]
-    Workers32,
-    #[cfg(testing)]
-    Workers3,
-    #[cfg(testing)]
-    Sahagins1,
-    #[cfg(testing)]
-    Sahagins2,
-    #[cfg(testing)]
-    Sahagins,
-    #[cfg(testing)]
-    MoonflowNorth,
-    #[cfg(testing)]
-    OakaShop,
-    #[cfg(testing)]
-    CrawlerGrid,
-    #[cfg(testing)]
-    SeymourGrid,
-    #[cfg(testing)]
-    WendigoGrid,
-    #[cfg(testing)]
-    Crevasse,
-    #[cfg(testing)]
-    BikanelParty,
-    #[cfg(testing)]
-    BikanelYeet,
-    #[cfg(testing)]
-    Bombs,
-    #[cfg(testing)]
-    DualHorns,
-    #[cfg(testing)]
-    Chimeras,
-    #[cfg(testing)]
-    Guards1,
-    #[cfg(testing)]
-    Guards2,
-    #[cfg(testing)]
-    Guards3,
-    #[cfg(testing)]
-    Guards4,
-    #[cfg(testing)]
-    Altana,
-    #[cfg(testing)]
-    ViaUnderwater,
-    #[cfg(testing)]
-    Defender,
-    #[cfg(testing)]
-    LevelSplit,
-}
-
-#[derive(Gui)]
-pub struct Settings {
-    /// Start the timer when a new run starts
-    #[default = true]
-    start: bool,
-
-    /// Enable autosplitting. See below for detailed splits
-    #[default = true]
-    split: bool,
-
-    /// Reset the timer on music selection
-    #[default = false]
-    reset: bool,
-
-    /// Remove load times from Game Time
-    #[default = true]
-    remove_loads: bool,
-
-    /// Count encounters, use Text layout to read the `encounter_count` value
-    #[default = true]
-    count_encounters: bool,
-
-    /// Splits: Enable the settings that match your splits!
-    _splits_heading1: Heading,
-
-    /// You don't need to enable all, only what you want to split.
-    _splits_heading2: Heading,
-
-    /// Anything else not mentioned here can still be split manually.
-    _splits_heading3: Heading,
-
-    /// Sinspawn Ammes
-    #[default = true]
-    ammes: bool,
-
-    /// Klikk
-    #[default = true]
-    klikk: bool,
-
-    /// Tros
-    #[default = true]
-    tros: bool,
-
-    /// Piranhas
-    #[default = true]
-    lagoon: bool,
-
-    /// Besaid Village
-    #[default = false]
-    besaid_village: bool,
-
-    /// Kimahri
-    #[default = true]
-    kimahri: bool,
-
-    /// Leaving Besaid Road
-    #[default = false]
-    besaid_road: bool,
-
-    /// Sinspawn Echuilles
-    #[default = true]
-    echuilles: bool,
-
-    /// Sinspawn Geneaux
-    #[default = true]
-    geneaux: bool,
-
-    /// Kilika Trials
-    #[default = false]
-    ifrit: bool,
-
-    /// Kilika Woods
-    #[default = true]
-    kilika: bool,
-
-    /// Oblitzerator
-    #[default = true]
-    oblitzerator: bool,
-
-    /// Before Blitzball
-    #[default = false]
-    before_blitzball: bool,
-
-    /// Blitzball Complete
-    #[default = true]
-    blitzball_done: bool,
-
-    /// Garuda
-    #[default = true]
-    garuda: bool,
-
-    /// Mi'ihen Highroad
-    #[default = true]
-    miihen_road: bool,
-
-    /// Chocobo Eater
-    #[default = false]
-    chocobo_eater: bool,
-
-    /// Old Road
-    #[default = true]
-    old_road: bool,
-
-    /// Mushroom Rock Road (Not with MRR skip)
-    #[default = true]
-    mrr: bool,
-
-    /// Sinspawn Gui (Not with MRR skip)
-    #[default = true]
-    gui: bool,
-
-    /// MRR Skip
-    #[default = false]
-    mrr_skip: bool,
-
-    /// Djose Highroad
-    #[default = true]
-    djose_road: bool,
-
-    /// Djose Trials
-    #[default = false]
-    ixion: bool,
-
-    /// Entering Moonflow
-    #[default = false]
-    enter_moonflow: bool,
-
-    /// Moonflow South
-    #[default = true]
-    moonflow_south: bool,
-
-    /// Extractor
-    #[default = true]
-    extractor: bool,
-
-    /// Guadosalam
-    #[default = true]
-    guadosalam: bool,
-
-    /// Thunder Plains
-    #[default = true]
-    thunder_plains: bool,
-
-    /// Macalnia Woods
-    #[default = false]
-    macalania_woods: bool,
-
-    /// Spherimorph
-    #[default = true]
-    spherimorph: bool,
-
-    /// Crawler
-    #[default = true]
-    crawler: bool,
-
-    /// Seymour
-    #[default = true]
-    seymour: bool,
-
-    /// Macalania Trials
-    #[default = false]
-    shiva: bool,
-
-    /// Wendigo
-    #[default = true]
-    wendigo: bool,
-
-    /// Bikanel
-    #[default = false]
-    bikanel: bool,
-
-    /// Home
-    #[default = true]
-    home: bool,
-
-    /// Evrae
-    #[default = true]
-    evrae: bool,
-
-    /// Bevelle Guards
-    #[default = true]
-    guards: bool,
-
-    /// Bevelle Trials
-    #[default = true]
-    bahamut: bool,
-
-    /// Via Purifico
-    #[default = false]
-    via_purifico: bool,
-
-    /// Isaaru
-    #[default = true]
-    isaaru: bool,
-
-    /// Seymour Natus
-    #[default = true]
-    natus: bool,
-
-    /// Calm Lands
-    #[default = false]
-    calm_lands: bool,
-
-    /// Biran & Yenke
-    #[default = true]
-    biran_yenke: bool,
-
-    /// Seymour Flux
-    #[default = true]
-    flux: bool,
-
-    /// Sanctuary Keeper
-    #[default = true]
-    sanctuary_keeper: bool,
-
-    /// Zanarkand
-    #[default = false]
-    zanarkand: bool,
-
-    /// Zanarkand Trials (Tetris)
-    #[default = false]
-    tetris: bool,
-
-    /// Spectral Keeper
-    #[default = false]
-    spectral_keeper: bool,
-
-    /// Yunalesca
-    #[default = true]
-    yunalesca: bool,
-
-    /// Sin Core
-    #[default = true]
-    core: bool,
-
-    /// Overdrive Sin
-    #[default = true]
-    overdrive: bool,
-
-    /// Seymour Omnis
-    #[default = true]
-    omnis: bool,
-
-    /// The Nucleus
-    #[default = false]
-    eggs: bool,
-
-    /// Braska's Final Aeon
-    #[default = true]
-    bfa: bool,
-
-    /// Yu Yevon
-    #[default = true]
-    yu_yevon: bool,
-
-    #[cfg(testing)]
-    /// FOR TESTING: The rest of the settings is for testing things
-    _test_heading: Heading,
-
-    #[cfg(testing)]
-    /// START: Start timer when loading a save
-    #[default = false]
-    start_on_load: bool,
-
-    #[cfg(testing)]
-    /// STARFT: Start timer when a battle starts
-    #[default = false]
-    start_on_battle: bool,
-
-    #[cfg(testing)]
-    /// START: Start timer at the beginning of Bevelle guards
-    #[default = false]
-    start_on_guards: bool,
-
-    #[cfg(testing)]
-    /// RESET: Reset timer when loading a regular save
-    #[default = false]
-    reset_on_load: bool,
-
-    #[cfg(testing)]
-    /// RESET: Reset timer when loading the auto save
-    #[default = false]
-    reset_on_autosave_load: bool,
-
-    #[cfg(testing)]
-    /// SPLIT: split everytime the level changes
-    #[default = false]
-    split_on_level: bool,
-
-    #[cfg(testing)]
-    /// Sin's Fin
-    #[default = false]
-    sinfin: bool,
-
-    #[cfg(testing)]
-    /// Yuna gets kidnapped in Luca
-    #[default = false]
-    luca: bool,
-
-    #[cfg(testing)]
-    /// First Workers
-    #[default = false]
-    workers1: bool,
-
-    #[cfg(testing)]
-    /// Second Workers
-    #[default = false]
-    workers2: bool,
-
-    #[cfg(testing)]
-    /// Third Workers, wave 1
-    #[default = false]
-    workers31: bool,
-
-    #[cfg(testing)]
-    /// Third Workers, wave 2
-    #[default = false]
-    workers32: bool,
-
-    #[cfg(testing)]
-    /// Third Workers
-    #[default = false]
-    workers3: bool,
-
-    #[cfg(testing)]
-    /// Sahagins wave 1
-    #[default = false]
-    sahagins1: bool,
-
-    #[cfg(testing)]
-    /// Sahagins wave 2
-    #[default = false]
-    sahagins2: bool,
-
-    #[cfg(testing)]
-    /// Sahagin Chiefs
-    #[default = false]
-    sahagins: bool,
-
-    #[cfg(testing)]
-    /// Moonflow North
-    #[default = false]
-    moonflow_north: bool,
-
-    #[cfg(testing)]
-    /// O'aka shop
-    #[default = false]
-    oaka_shop: bool,
-
-    #[cfg(testing)]
-    /// Pre-Crawler grid
-    #[default = false]
-    crawler_grid: bool,
-
-    #[cfg(testing)]
-    /// Pre-Seymour grid
-    #[default = false]
-    seymour_grid: bool,
-
-    #[cfg(testing)]
-    /// Pre-Wendigo grid
-    #[default = false]
-    wendigo_grid: bool,
-
-    #[cfg(testing)]
-    /// Crevasse
-    #[default = false]
-    crevasse: bool,
-
-    #[cfg(testing)]
-    /// Bikanel party members joined
-    #[default = false]
-    bikanel_party: bool,
-
-    #[cfg(testing)]
-    /// Bikanel Sandragora yeeted
-    #[default = false]
-    bikanel_yeet: bool,
-
-    #[cfg(testing)]
-    /// Home Bombs
-    #[default = false]
-    bombs: bool,
-
-    #[cfg(testing)]
-    /// Home Dual Horns
-    #[default = false]
-    dual_horns: bool,
-
-    #[cfg(testing)]
-    /// Home Chimeras
-    #[default = false]
-    chimeras: bool,
-
-    #[cfg(testing)]
-    /// Bevelle Guards #1
-    #[default = false]
-    guards1: bool,
-
-    #[cfg(testing)]
-    /// Bevelle Guards #2
-    #[default = false]
-    guards2: bool,
-
-    #[cfg(testing)]
-    /// Bevelle Guards #3
-    #[default = false]
-    guards3: bool,
-
-    #[cfg(testing)]
-    /// Bevelle Guards #4
-    #[default = false]
-    guards4: bool,
-
-    #[cfg(testing)]
-    /// Evrae Altana
-    #[default = false]
-    altana: bool,
-
-    #[cfg(testing)]
-    /// Escaped Via Purifico Underwater
-    #[default = false]
-    via_underwater: bool,
-
-    #[cfg(testing)]
-    /// Defender
-    #[default = false]
-    defender: bool,
+#[doc(hidden)]
+pub fn print_message(message: &str) {
+    #[cfg(target_family = "wasm")]
+    asr::print_message(message);
+    #[cfg(not(target_family = "wasm"))]
+    std::eprintln!("{message}");
 }
 
-impl Settings {
-    fn filter(&self, split_on: Splits) -> bool {
-        let Settings {
-            start: _,
-            split: _,
-            reset: _,
-            remove_loads: _,
-            count_encounters: _,
-            _splits_heading1,
-            _splits_heading2,
-            _splits_heading3,
-            ammes,
-            klikk,
-            tros,
-            lagoon,
-            besaid_village,
-            kimahri,
-            besaid_road,
-            echuilles,
-            geneaux,
-            ifrit,
-            kilika,
-            oblitzerator,
-            before_blitzball,
-            blitzball_done,
-            garuda,
-            miihen_road,
-            chocobo_eater,
-            old_road,
-            mrr,
-            gui,
-            mrr_skip,
-            djose_road,
-            ixion,
-            enter_moonflow,
-            moonflow_south,
-            extractor,
-            guadosalam,
-            thunder_plains,
-            macalania_woods,
-            spherimorph,
-            crawler,
-            seymour,
-            shiva,
-            wendigo,
-            bikanel,
-            home,
-            evrae,
-            guards,
-            bahamut,
-            via_purifico,
-            isaaru,
-            natus,
-            calm_lands,
-            biran_yenke,
-            flux,
-            sanctuary_keeper,
-            zanarkand,
-            tetris,
-            spectral_keeper,
-            yunalesca,
-            core,
-            overdrive,
-            omnis,
-            eggs,
-            bfa,
-            yu_yevon,
-            #[cfg(testing)]
-            _test_heading,
-            #[cfg(testing)]
-                start_on_load: _,
-            #[cfg(testing)]
-                start_on_battle: _,
-            #[cfg(testing)]
-                start_on_guards: _,
-            #[cfg(testing)]
-                reset_on_load: _,
-            #[cfg(testing)]
-                reset_on_autosave_load: _,
-            #[cfg(testing)]
-            split_on_level,
-            #[cfg(testing)]
-            sinfin,
-            #[cfg(testing)]
-            luca,
-            #[cfg(testing)]
-            workers1,
-            #[cfg(testing)]
-            workers2,
-            #[cfg(testing)]
-            workers31,
-            #[cfg(testing)]
-            workers32,
-            #[cfg(testing)]
-            workers3,
-            #[cfg(testing)]
-            sahagins1,
-            #[cfg(testing)]
-            sahagins2,
-            #[cfg(testing)]
-            sahagins,
-            #[cfg(testing)]
-            moonflow_north,
-            #[cfg(testing)]
-            oaka_shop,
-            #[cfg(testing)]
-            crawler_grid,
-            #[cfg(testing)]
-            seymour_grid,
-            #[cfg(testing)]
-            wendigo_grid,
-            #[cfg(testing)]
-            crevasse,
-            #[cfg(testing)]
-            bikanel_party,
-            #[cfg(testing)]
-            bikanel_yeet,
-            #[cfg(testing)]
-            bombs,
-            #[cfg(testing)]
-            dual_horns,
-            #[cfg(testing)]
-            chimeras,
-            #[cfg(testing)]
-            guards1,
-            #[cfg(testing)]
-            guards2,
-            #[cfg(testing)]
-            guards3,
-            #[cfg(testing)]
-            guards4,
-            #[cfg(testing)]
-            altana,
-            #[cfg(testing)]
-            via_underwater,
-            #[cfg(testing)]
-            defender,
-        } = self;
+#[cfg(testing)]
+const SPLIT_COOLDOWN_SECS: i32 = 5;
 
-        return *match split_on {
-            Splits::Ammes => ammes,
-            Splits::Klikk => klikk,
-            Splits::Tros => tros,
-            Splits::Lagoon => lagoon,
-            Splits::BesaidVillage => besaid_village,
-            Splits::Kimahri => kimahri,
-            Splits::BesaidRoad => besaid_road,
-            Splits::Echuilles => echuilles,
-            Splits::Geneaux => geneaux,
-            Splits::Ifrit => ifrit,
-            Splits::Kilika => kilika,
-            Splits::Oblitzerator => oblitzerator,
-            Splits::BeforeBlitzball => before_blitzball,
-            Splits::BlitzballDone => blitzball_done,
-            Splits::Garuda => garuda,
-            Splits::MiihenRoad => miihen_road,
-            Splits::ChocoboEater => chocobo_eater,
-            Splits::OldRoad => old_road,
-            Splits::Mrr => mrr,
-            Splits::Gui => gui,
-            Splits::MrrSkip => mrr_skip,
-            Splits::DjoseRoad => djose_road,
-            Splits::Ixion => ixion,
-            Splits::EnterMoonflow => enter_moonflow,
-            Splits::MoonflowSouth => moonflow_south,
-            Splits::Extractor => extractor,
-            Splits::Guadosalam => guadosalam,
-            Splits::ThunderPlains => thunder_plains,
-            Splits::MacalaniaWoods => macalania_woods,
-            Splits::Spherimorph => spherimorph,
-            Splits::Crawler => crawler,
-            Splits::Seymour => seymour,
-            Splits::Shiva => shiva,
-            Splits::Wendigo => wendigo,
-            Splits::Bikanel => bikanel,
-            Splits::Home => home,
-            Splits::Evrae => evrae,
-            Splits::Guards => guards,
-            Splits::Bahamut => bahamut,
-            Splits::ViaPurifico => via_purifico,
-            Splits::Isaaru => isaaru,
-            Splits::Natus => natus,
-            Splits::CalmLands => calm_lands,
-            Splits::BiranYenke => biran_yenke,
-            Splits::Flux => flux,
-            Splits::SanctuaryKeeper => sanctuary_keeper,
-            Splits::Zanarkand => zanarkand,
-            Splits::Tetris => tetris,
-            Splits::SpectralKeeper => spectral_keeper,
-            Splits::Yunalesca => yunalesca,
-            Splits::Core => core,
-            Splits::Overdrive => overdrive,
-            Splits::Omnis => omnis,
-            Splits::Eggs => eggs,
-            Splits::Bfa => bfa,
-            Splits::YuYevon => yu_yevon,
-            #[cfg(testing)]
-            Splits::SinFin => sinfin,
-            #[cfg(testing)]
-            Splits::Luca => luca,
-            #[cfg(testing)]
-            Splits::Workers1 => workers1,
-            #[cfg(testing)]
-            Splits::Workers2 => workers2,
-            #[cfg(testing)]
-            Splits::Workers31 => workers31,
-            #[cfg(testing)]
-            Splits::Workers32 => workers32,
-            #[cfg(testing)]
-            Splits::Workers3 => workers3,
-            #[cfg(testing)]
-            Splits::Sahagins1 => sahagins1,
-            #[cfg(testing)]
-            Splits::Sahagins2 => sahagins2,
-            #[cfg(testing)]
-            Splits::Sahagins => sahagins,
-            #[cfg(testing)]
-            Splits::MoonflowNorth => moonflow_north,
-            #[cfg(testing)]
-            Splits::OakaShop => oaka_shop,
-            #[cfg(testing)]
-            Splits::CrawlerGrid => crawler_grid,
-            #[cfg(testing)]
-            Splits::SeymourGrid => seymour_grid,
-            #[cfg(testing)]
-            Splits::WendigoGrid => wendigo_grid,
-            #[cfg(testing)]
-            Splits::Crevasse => crevasse,
-            #[cfg(testing)]
-            Splits::BikanelParty => bikanel_party,
-            #[cfg(testing)]
-            Splits::BikanelYeet => bikanel_yeet,
-            #[cfg(testing)]
-            Splits::Bombs => bombs,
-            #[cfg(testing)]
-            Splits::DualHorns => dual_horns,
-            #[cfg(testing)]
-            Splits::Chimeras => chimeras,
-            #[cfg(testing)]
-            Splits::Guards1 => guards1,
-            #[cfg(testing)]
-            Splits::Guards2 => guards2,
-            #[cfg(testing)]
-            Splits::Guards3 => guards3,
-            #[cfg(testing)]
-            Splits::Guards4 => guards4,
-            #[cfg(testing)]
-            Splits::Altana => altana,
-            #[cfg(testing)]
-            Splits::ViaUnderwater => via_underwater,
-            #[cfg(testing)]
-            Splits::Defender => defender,
-            #[cfg(testing)]
-            Splits::LevelSplit => split_on_level,
-        };
+// Every split, with its setting and what triggers it.
+// Generates `Splits`, `Settings` and `Settings::filter`, see `split_table`.
+splits! {
+    options {
+        /// Start the timer when a new run starts
+        start = true,
+        /// Enable autosplitting. See below for detailed splits
+        split = true,
+        /// Reset the timer on music selection
+        reset = false,
+        /// Undo splits when a game over loads a save from before them
+        undo_splits = false,
+        /// Remove load times from Game Time
+        remove_loads = true,
+        /// Use the in-game time as Game Time, instead of removing loads
+        use_igt = false,
+        /// Pause Game Time while the game is closed, e.g. after a crash
+        pause_on_crash = false,
+        /// Count encounters, use Text layout to read the `encounter_count` value
+        count_encounters = true,
+        /// Record a trace of all game values to the log (for bug reports)
+        record_trace = false,
+    }
+    splits {
+        /// Sinspawn Ammes
+        Ammes(ammes = true) => [battle(Progress::AMMES)],
+        /// Klikk
+        Klikk(klikk = true) => [battle(Progress::KLIKK).fanfare()],
+        /// Tros
+        Tros(tros = true) => [battle(Progress::TROS).at(4, 0, 0)],
+        /// Piranhas
+        Lagoon(lagoon = true) => [
+            level(Level::LAGOON, Level::BESAID_PROMONTORY),
+            level(Level::LAGOON, Level::BESAID_VILLAGE_ROAD),
+            advance(Progress::LAGOON).during(73),
+        ], // story 119->124
+        /// Besaid Village
+        BesaidVillage(besaid_village = false) => [
+            level(Level::BESAID_VILLAGE, Level::BESAID_VILLAGE_ROAD).story(Progress::BESAID_VILLAGE),
+        ],
+        /// Kimahri
+        Kimahri(kimahri = true) => [battle(Progress::KIMAHRI)],
+        /// Leaving Besaid Road
+        BesaidRoad(besaid_road = false) => [
+            level(Level::BESAID_END_ROAD, Level::BESAID_BEACH).story(Progress::BESAID_ROAD),
+        ],
+        /// Sinspawn Echuilles
+        Echuilles(echuilles = true) => [battle(Progress::ECHUILLES)],
+        /// Sinspawn Geneaux
+        Geneaux(geneaux = true) => [battle(Progress::GENEAUX)],
+        /// Kilika Trials
+        Ifrit(ifrit = false) => [
+            level(Level::KILIKA_FAYTH, Level::KILIKA_TEMPLE),
+        ], // story 346 -> 348
+        /// Kilika Woods
+        Kilika(kilika = true) => [level(Level::KILIKA_WOODS, Level::KILIKA_RESIDENTIAL_AREA)],
+        /// Oblitzerator
+        Oblitzerator(oblitzerator = true) => [battle(Progress::OBLITZERATOR)],
+        /// Before Blitzball
+        BeforeBlitzball(before_blitzball = false) => [
+            level(Level::LUCA_DOCK_5, Level::LUCA_MAIN).story(Progress::BLITZBALL),
+        ],
+        /// Blitzball Complete
+        BlitzballDone(blitzball_done = true) => [level(Level::STADIUM_POOL, Level::STADIUM_STANDS)],
+        /// Garuda
+        Garuda(garuda = true) => [battle(Progress::GARUDA).at(17, 0, 1)],
+        /// Mi'ihen Highroad
+        MiihenRoad(miihen_road = true) => [level(Level::HIGHROAD_CENTRAL, Level::HIGHROAD_AGENCY)],
+        /// Chocobo Eater
+        ChocoboEater(chocobo_eater = false) => [battle(Progress::CHOCOBO_EATER).escapable()],
+        /// Old Road
+        OldRoad(old_road = true) => [level(Level::HIGHROAD_NORTH_END, Level::MUSHROOM_ROCK_ROAD)],
+        /// Mushroom Rock Road (Not with MRR skip)
+        Mrr(mrr = true) => [advance(Progress::MRR).during(940)],
+        /// Sinspawn Gui (Not with MRR skip)
+        Gui(gui = true) => [battle(Progress::GUI)],
+        /// MRR Skip
+        MrrSkip(mrr_skip = false) => [
+            level(Level::MUSHROOM_ROCK_ROAD, Level::MUSHROOM_ROCK_AFTERMATH),
+        ],
+        /// Djose Highroad
+        DjoseRoad(djose_road = true) => [
+            level(Level::DJOSE_HIGHROAD, Level::DJOSE_PILGRIMAGE_ROAD),
+        ],
+        /// Djose Trials
+        Ixion(ixion = false) => [
+            level(Level::DJOSE_FAYTH, Level::DJOSE_OUTSIDE).story(Progress::IXION),
+        ],
+        /// Entering Moonflow
+        EnterMoonflow(enter_moonflow = false) => [
+            level(Level::DJOSE_HIGHROAD, Level::MOONFLOW_SOUTH_BANK_ROAD).story(Progress::ENTER_MOONFLOW),
+        ],
+        /// Moonflow South
+        MoonflowSouth(moonflow_south = true) => [
+            level(Level::MOONFLOW_SOUTH_BANK_ROAD, Level::MOONFLOW_SOUTH_BANK),
+        ],
+        /// Extractor
+        Extractor(extractor = true) => [battle(Progress::EXTRACTOR)],
+        /// Guadosalam
+        Guadosalam(guadosalam = true) => [level(Level::GUADOSALAM, Level::THUNDERPLAINS_SOUTH)],
+        /// Thunder Plains
+        ThunderPlains(thunder_plains = true) => [
+            level(Level::THUNDERPLAINS_NORTH, Level::MACALANIA_WOODS_SOUTH),
+        ],
+        /// Macalnia Woods
+        MacalaniaWoods(macalania_woods = false) => [
+            level(Level::MACALANIA_WOODS_NORTH, Level::MACALANIA_SPRING).story(Progress::MACALANIA_WOODS),
+        ],
+        /// Spherimorph
+        Spherimorph(spherimorph = true) => [battle(Progress::SPHERIMORPH)],
+        /// Crawler
+        Crawler(crawler = true) => [battle(Progress::CRAWLER)],
+        /// Seymour
+        Seymour(seymour = true) => [battle(Progress::SEYMOUR)],
+        /// Macalania Trials
+        Shiva(shiva = false) => [
+            level(Level::MACALANIA_TEMPLE, Level::MACALANIA_TEMPLE_ROAD).story(Progress::SHIVA),
+        ],
+        /// Wendigo
+        Wendigo(wendigo = true) => [battle(Progress::WENDIGO).at(44, 0, 1)],
+        /// Bikanel
+        Bikanel(bikanel = false) => [
+            level(Level::BIKANEL_NORTH, Level::HOME_ENTRANCE),
+        ], // story 1720 -> 1800
+        /// Home
+        Home(home = true) => [
+            level(Level::HOME_MAIN_CORRIDOR, Level::HOME_ENVIRONMENT_CONTROLS).story(Progress::HOME_CONTROLS),
+        ],
+        /// Evrae
+        Evrae(evrae = true) => [battle(Progress::EVRAE)],
+        /// Bevelle Guards
+        Guards(guards = true) => [battle(Progress::GUARDS).at(53, 0, 2)],
+        /// Bevelle Trials
+        Bahamut(bahamut = true) => [level(Level::BEVELLE_TRIALS, Level::BEVELLE_ANTECHAMBER)],
+        /// Via Purifico
+        ViaPurifico(via_purifico = false) => [cutscene(Progress::ISAARU, 18, 257).at(54, 1, 0)],
+        /// Isaaru
+        Isaaru(isaaru = true) => [battle(Progress::ISAARU).at(54, 2, 2)],
+        /// Seymour Natus
+        Natus(natus = true) => [battle(Progress::NATUS)],
+        /// Calm Lands
+        CalmLands(calm_lands = false) => [
+            level(Level::CALM_LANDS, Level::CALM_LANDS_BRIDGE).story(Progress::CALM_LANDS),
+        ],
+        /// Biran & Yenke
+        BiranYenke(biran_yenke = true) => [battle(Progress::BIRAN_YENKE).fanfare()],
+        /// Seymour Flux
+        Flux(flux = true) => [battle(Progress::FLUX).at(65, 0, 0)],
+        /// Sanctuary Keeper
+        SanctuaryKeeper(sanctuary_keeper = true) => [
+            battle(Progress::SANCTUARY_KEEPER).at(68, 0, 0),
+        ],
+        /// Zanarkand
+        Zanarkand(zanarkand = false) => [
+            level(Level::ZANARKAND_ROAD, Level::ZANARKAND_DOME).story(Progress::ZANARKAND),
+        ],
+        /// Zanarkand Trials (Tetris)
+        Tetris(tetris = false) => [advance(Progress::TETRIS).to(Progress::SPECTRAL_KEEPER)],
+        /// Spectral Keeper
+        SpectralKeeper(spectral_keeper = false) => [battle(Progress::SPECTRAL_KEEPER)],
+        /// Yunalesca
+        Yunalesca(yunalesca = true) => [battle(Progress::YUNALESCA)],
+        /// Sin Core
+        Core(core = true) => [battle(Progress::SIN_CORE)],
+        /// Overdrive Sin
+        Overdrive(overdrive = true) => [battle(Progress::OVERDRIVE_SIN)],
+        /// Seymour Omnis
+        Omnis(omnis = true) => [battle(Progress::OMNIS).at(78, 0, 0)],
+        /// The Nucleus
+        Eggs(eggs = false) => [level(Level::NUCLEUS, Level::DREAMS_END).story(Progress::EGGS)],
+        /// Braska's Final Aeon
+        Bfa(bfa = true) => [battle(Progress::BFA)],
+        /// Yu Yevon
+        YuYevon(yu_yevon = true) => [hp_zero(Progress::YU_YEVON)],
+    }
+    custom_splits {
+        Custom1 {
+            /// Custom split 1
+            kind: custom1,
+            /// Custom split 1: From level (level transitions)
+            from: custom1_from,
+            /// Custom split 1: To level (level transitions)
+            to: custom1_to,
+            /// Custom split 1: Story progression (battle ends and story advances)
+            story: custom1_story,
+        },
+        Custom2 {
+            /// Custom split 2
+            kind: custom2,
+            /// Custom split 2: From level (level transitions)
+            from: custom2_from,
+            /// Custom split 2: To level (level transitions)
+            to: custom2_to,
+            /// Custom split 2: Story progression (battle ends and story advances)
+            story: custom2_story,
+        },
+        Custom3 {
+            /// Custom split 3
+            kind: custom3,
+            /// Custom split 3: From level (level transitions)
+            from: custom3_from,
+            /// Custom split 3: To level (level transitions)
+            to: custom3_to,
+            /// Custom split 3: Story progression (battle ends and story advances)
+            story: custom3_story,
+        },
+        Custom4 {
+            /// Custom split 4
+            kind: custom4,
+            /// Custom split 4: From level (level transitions)
+            from: custom4_from,
+            /// Custom split 4: To level (level transitions)
+            to: custom4_to,
+            /// Custom split 4: Story progression (battle ends and story advances)
+            story: custom4_story,
+        },
+        Custom5 {
+            /// Custom split 5
+            kind: custom5,
+            /// Custom split 5: From level (level transitions)
+            from: custom5_from,
+            /// Custom split 5: To level (level transitions)
+            to: custom5_to,
+            /// Custom split 5: Story progression (battle ends and story advances)
+            story: custom5_story,
+        },
+    }
+    testing_options {
+        /// START: Start timer when loading a save
+        start_on_load = false,
+        /// STARFT: Start timer when a battle starts
+        start_on_battle = false,
+        /// START: Start timer at the beginning of Bevelle guards
+        start_on_guards = false,
+        /// RESET: Reset timer when loading a regular save
+        reset_on_load = false,
+        /// RESET: Reset timer when loading the auto save
+        reset_on_autosave_load = false,
+    }
+    testing_splits {
+        /// SPLIT: split everytime the level changes
+        LevelSplit(split_on_level = false) => [],
+        /// Sin's Fin
+        SinFin(sinfin = false) => [battle(Progress::SINFIN)],
+        /// Yuna gets kidnapped in Luca
+        Luca(luca = false) => [
+            level(Level::LUCA_MAIN, Level::LUCA_DOCK_1).story(Progress::WORKERS),
+        ],
+        /// First Workers
+        Workers1(workers1 = false) => [battle(Progress::WORKERS).at(12, 0, 0)],
+        /// Second Workers
+        Workers2(workers2 = false) => [battle(Progress::WORKERS).at(13, 0, 0)],
+        /// Third Workers, wave 1
+        Workers31(workers31 = false) => [],
+        /// Third Workers, wave 2
+        Workers32(workers32 = false) => [],
+        /// Third Workers
+        Workers3(workers3 = false) => [battle(Progress::WORKERS).at(14, 0, 0)],
+        /// Sahagins wave 1
+        Sahagins1(sahagins1 = false) => [],
+        /// Sahagins wave 2
+        Sahagins2(sahagins2 = false) => [],
+        /// Sahagin Chiefs
+        Sahagins(sahagins = false) => [battle(Progress::SAHAGINS)],
+        /// Moonflow North
+        MoonflowNorth(moonflow_north = false) => [level(Level::MOONFLOW_NORTH, Level::GUADOSALAM)],
+        /// O'aka shop
+        OakaShop(oaka_shop = false) => [
+            level(Level::MACALANIA_SPRING, Level::MACALANIA_SPHERIMORPH),
+        ],
+        /// Pre-Crawler grid
+        CrawlerGrid(crawler_grid = false) => [
+            level(Level::MACALANIA_SPRING, Level::MACALNIA_LAKE_SHOP).story(Progress::CRAWLER_GRID),
+        ],
+        /// Pre-Seymour grid
+        SeymourGrid(seymour_grid = false) => [
+            level(Level::MACALANIA_TEMPLE_ROAD, Level::MACALANIA_TEMPLE).story(Progress::SEYMOUR_GRID),
+        ],
+        /// Pre-Wendigo grid
+        WendigoGrid(wendigo_grid = false) => [
+            level(Level::MACALNIA_ANTECHAMBER, Level::MACALNIA_HALLWAY),
+        ],
+        /// Crevasse
+        Crevasse(crevasse = false) => [level(Level::CREVASSE, Level::MACALNIA_LAKE)],
+        /// Bikanel party members joined
+        BikanelParty(bikanel_party = false) => [
+            advance(Progress::BIKANEL_KIMAHRI).to(Progress::BIKANEL_RIKKU),
+        ],
+        /// Bikanel Sandragora yeeted
+        BikanelYeet(bikanel_yeet = false) => [battle(Progress::BIKANEL_RIKKU).at(48, 2, 0)],
+        /// Home Bombs
+        Bombs(bombs = false) => [battle(Progress::HOME).at(87, 0, 0)],
+        /// Home Dual Horns
+        DualHorns(dual_horns = false) => [battle(Progress::HOME).at(87, 0, 2)],
+        /// Home Chimeras
+        Chimeras(chimeras = false) => [battle(Progress::HOME2).at(87, 0, 3)],
+        /// Bevelle Guards #1
+        Guards1(guards1 = false) => [battle(Progress::GUARDS).at(53, 0, 0)], // story = 2080
+        /// Bevelle Guards #2
+        Guards2(guards2 = false) => [battle(Progress::GUARDS).at(53, 0, 1)],
+        /// Bevelle Guards #3
+        Guards3(guards3 = false) => [],
+        /// Bevelle Guards #4
+        Guards4(guards4 = false) => [],
+        /// Evrae Altana
+        Altana(altana = false) => [battle(Progress::ISAARU).at(55, 1, 0)],
+        /// Escaped Via Purifico Underwater
+        ViaUnderwater(via_underwater = false) => [
+            level(Level::VIA_UNDERWATER, Level::HIGHBRIDGE).story(Progress::VIA_UNDERWATER),
+        ],
+        /// Defender
+        Defender(defender = false) => [battle(Progress::DEFENDER).at(61, 0, 0)],
     }
 }
 
 struct Running {
     splits: SeenSplits,
+    game_over: GameOver,
+    undo: Undo,
+    encounters: Encounters,
+    battle_times: BattleTimes,
+    loads: Loads,
+    summary: Summary,
+    custom: custom_splits::Rules,
+    /// The seen splits need to be rebuilt from the game, after attaching mid-run.
+    rebuild: bool,
+    /// When game time was paused because the game was closed.
+    closed_at: Option<Instant>,
     watchers: Watchers,
     #[cfg(testing)]
     workers3: u32,
@@ -858,6 +420,7 @@ struct Running {
 
 struct NotRunning {
     watchers: Watchers,
+    game_over: GameOver,
     loading_frame_buffer: u32,
     #[cfg(testing)]
     start_igt: u32,
@@ -867,6 +430,7 @@ impl NotRunning {
     fn new() -> Self {
         Self {
             watchers: Watchers::new(),
+            game_over: GameOver::default(),
             loading_frame_buffer: 0,
             #[cfg(testing)]
             start_igt: 0,
@@ -890,6 +454,15 @@ impl Timer {
             Self::NotRunning(_nr) => {
                 let running = Running {
                     splits: SeenSplits::empty(),
+                    game_over: _nr.game_over,
+                    undo: Undo::new(),
+                    encounters: Encounters::new(),
+                    battle_times: BattleTimes::new(),
+                    loads: Loads::new(),
+                    summary: Summary::new(),
+                    custom: custom_splits::Rules::new(),
+                    rebuild: true,
+                    closed_at: None,
                     watchers: Watchers::new(),
                     #[cfg(testing)]
                     workers3: 1,
@@ -911,8 +484,12 @@ impl Timer {
 
     fn stop(&mut self) -> &mut NotRunning {
         match self {
-            Self::Running(_) => {
-                let not_running = NotRunning::new();
+            Self::Running(running) => {
+                running.encounters.finish();
+                running.battle_times.finish();
+                running.loads.finish();
+                let mut not_running = NotRunning::new();
+                not_running.game_over = running.game_over;
                 *self = Self::NotRunning(not_running);
                 let Self::NotRunning(not_running) = self else {
                     unreachable!();
@@ -922,6 +499,51 @@ impl Timer {
             Self::NotRunning(not_running) => not_running,
         }
     }
+
+    fn update(
+        &mut self,
+        settings: &Settings,
+        timer_state: TimerState,
+        game: &impl GameMemory,
+        control: &mut impl TimerControl,
+    ) -> Action {
+        match timer_state {
+            TimerState::Running | TimerState::Paused => {
+                let running = self.get_or_start();
+                let action = running.update_game(settings, game, control);
+                if matches!(action, Action::Restart) {
+                    // the restarted run starts from scratch
+                    self.stop();
+                }
+                return action;
+            }
+            TimerState::NotRunning | TimerState::Ended => {
+                let not_running = self.stop();
+                return not_running.update_game(settings, game, control);
+            }
+            otherwise => {
+                log!("Unexpected timer state: {:?}", otherwise);
+                return Action::Nothing;
+            }
+        }
+    }
+
+    fn reattach(&mut self, settings: &Settings, control: &mut impl TimerControl) {
+        if let Self::Running(running) = self {
+            running.rebuild = true;
+            if settings.pause_on_crash && running.closed_at.is_none() {
+                log!("Game closed, pausing game time");
+                control.pause_game_time();
+                running.closed_at = Some(Instant::now());
+            }
+            #[cfg(testing)]
+            {
+                running.workers3 = 1;
+                running.sahagins = 0;
+                running.guards = 1;
+            }
+        }
+    }
 }
 
 struct Game {
@@ -934,6 +556,9 @@ struct State<'s> {
     settings: &'s mut Settings,
     timer: Timer,
     game: Option<Game>,
+    recorder: trace::Recorder,
+    presets: Presets,
+    lss_import: lss::Import,
 }
 
 async fn main() {
@@ -946,10 +571,15 @@ async fn main() {
         s
     };
 
+    let presets = Presets::new(&settings);
+    let lss_import = lss::Import::new(&settings);
     let mut state = State {
         settings: &mut settings,
         timer: Timer::new(),
         game: None,
+        recorder: trace::Recorder::new(),
+        presets,
+        lss_import,
     };
 
     loop {
@@ -964,29 +594,36 @@ enum Action {
     Start,
     StartPaused,
     Reset,
+    /// Reset and start the timer again.
+    Restart,
     Split(Splits),
 }
 
 impl Action {
-    fn send(self) {
+    fn send(self, timer: &mut impl TimerControl) {
         match self {
             Self::Nothing => {}
             Self::Start => {
                 log!("Timer START!");
-                timer::start();
+                timer.start();
             }
             Self::StartPaused => {
                 log!("Timer START!");
-                timer::start();
-                timer::pause_game_time();
+                timer.start();
+                timer.pause_game_time();
             }
             Self::Reset => {
                 log!("Timer RESET!");
-                timer::reset();
+                timer.reset();
+            }
+            Self::Restart => {
+                log!("Timer RESTART!");
+                timer.reset();
+                timer.start();
             }
             Self::Split(splits) => {
                 log!("SPLIT! {:?}", splits);
-                timer::split();
+                timer.split();
             }
         }
     }
@@ -1020,45 +657,58 @@ impl State<'_> {
             .await
             .unwrap_or_default();
 
-        #[cfg(testing)]
-        if let Timer::Running(ref mut running) = self.timer {
-            running.workers3 = 1;
-            running.sahagins = 0;
-            running.guards = 1;
-        }
+        self.recorder.restart();
+        self.timer.reattach(self.settings, &mut AsrTimer);
     }
 
     async fn main_loop(&mut self, process: &Process, memory: &Memory) {
+        let game = ProcessMemory::new(process, memory);
         loop {
-            let action = self.tick(process, memory);
-            action.send();
+            self.tick(&game);
             next_tick().await;
-            self.settings.update();
+            self.update_settings();
         }
     }
 
-    fn tick(&mut self, process: &Process, memory: &Memory) -> Action {
-        let timer_state = timer::state();
-        match timer_state {
-            TimerState::Running | TimerState::Paused => {
-                let running = self.timer.get_or_start();
-                return running.update_game(self.settings, process, memory);
-            }
-            TimerState::NotRunning | TimerState::Ended => {
-                let not_running = self.timer.stop();
-                return not_running.update_game(self.settings, process, memory);
-            }
-            otherwise => {
-                log!("Unexpected timer state: {:?}", otherwise);
-                return Action::Nothing;
-            }
-        }
+    fn update_settings(&mut self) {
+        self.settings.update();
+        self.presets.update(self.settings);
+        self.lss_import.update(self.settings, &mut AsrTimer);
+    }
+
+    fn tick(&mut self, game: &impl GameMemory) {
+        let mut control = AsrTimer;
+        let timer_state = control.state();
+        let action = if self.settings.record_trace {
+            // the splitter runs on the recorded values, so that the trace has
+            // exactly what it saw, and not what was read again afterwards
+            let values = FakeMemory::capture(game);
+            let action = self
+                .timer
+                .update(self.settings, timer_state, &values, &mut control);
+            self.recorder.record(&values, timer_state, action);
+            action
+        } else {
+            self.recorder.restart();
+            self.timer
+                .update(self.settings, timer_state, game, &mut control)
+        };
+        action.send(&mut control);
     }
 }
 
 impl NotRunning {
-    fn update_game(&mut self, settings: &Settings, process: &Process, memory: &Memory) -> Action {
-        let mut read = Read::new(&mut self.watchers, process, memory);
+    fn update_game(
+        &mut self,
+        settings: &Settings,
+        game: &impl GameMemory,
+        control: &mut impl TimerControl,
+    ) -> Action {
+        let mut read = Read::new(&mut self.watchers, game);
+        Level::update_zone(&mut read, control);
+        battles::update(&mut read, control);
+        battles::update_enemy_hp(&mut read, control);
+        self.game_over.update(&mut read, control);
         let splitter = Self::try_update_game(settings, &mut read, &mut self.loading_frame_buffer);
         #[cfg(testing)]
         if matches!(&splitter, Action::Start) {
@@ -1070,7 +720,7 @@ impl NotRunning {
 
     fn try_update_game(
         settings: &Settings,
-        read: &mut Read,
+        read: &mut Read<'_, impl GameMemory>,
         loading_frame_buffer: &mut u32,
     ) -> Action {
         let level = read.level();
@@ -1152,18 +802,71 @@ impl NotRunning {
 }
 
 impl Running {
-    fn update_game(&mut self, settings: &Settings, process: &Process, memory: &Memory) -> Action {
-        match self.find_split(settings, process, memory) {
+    fn update_game(
+        &mut self,
+        settings: &Settings,
+        game: &impl GameMemory,
+        control: &mut impl TimerControl,
+    ) -> Action {
+        match self.find_split(settings, game, control) {
             ControlFlow::Break(split) => self
-                .try_split(settings, split)
+                .try_split(settings, split, control)
                 .map_or(Action::Nothing, Action::Split),
             ControlFlow::Continue(action) => action,
         }
     }
 
-    fn find_split(&mut self, settings: &Settings, process: &Process, memory: &Memory) -> Splitter {
-        let mut read = Read::new(&mut self.watchers, process, memory);
-        let splitter = Self::try_find_split(settings, &mut read);
+    fn find_split(
+        &mut self,
+        settings: &Settings,
+        game: &impl GameMemory,
+        control: &mut impl TimerControl,
+    ) -> Splitter {
+        let mut read = Read::new(&mut self.watchers, game);
+        Level::update_zone(&mut read, control);
+        battles::update(&mut read, control);
+        battles::update_enemy_hp(&mut read, control);
+        self.battle_times.update(&mut read, control);
+        self.summary.update(&mut read);
+        let game_over = self.game_over.update(&mut read, control);
+        if game_over {
+            match settings.game_over {
+                GameOverAction::Nothing => {}
+                GameOverAction::Reset => return RESET,
+                GameOverAction::Restart => return ControlFlow::Continue(Action::Restart),
+            }
+        }
+        if self.rebuild {
+            self.rebuild = Self::rebuild_splits(&mut self.splits, &mut read) == false;
+        }
+        if let Some(closed_at) = self.closed_at {
+            if Self::game_loaded(&mut read) {
+                log!(
+                    "Game is back, resuming game time after {:?}",
+                    closed_at.elapsed()
+                );
+                if settings.use_igt == false {
+                    control.resume_game_time();
+                }
+                self.closed_at = None;
+            }
+        }
+        let undone = self
+            .undo
+            .update(settings, game_over, &mut read, &mut self.splits, control);
+        for split in undone {
+            self.encounters.undo(split, control);
+            self.summary.undo(split);
+        }
+        let next = Splits::iter().find(|split| {
+            split.setting_key().is_some()
+                && settings.filter(*split)
+                && self.splits.contains(split) == false
+        });
+        self.encounters.update(settings, &mut read, next, control);
+        self.custom.update(settings);
+        let splitter =
+            Self::try_find_split(settings, &self.custom, &mut read, &mut self.loads, control);
 
         match &splitter {
             #[cfg(testing)]
@@ -1185,14 +888,29 @@ impl Running {
         return splitter;
     }
 
-    fn try_find_split(settings: &Settings, read: &mut Read) -> Splitter {
-        if settings.remove_loads {
+    fn try_find_split(
+        settings: &Settings,
+        custom: &custom_splits::Rules,
+        read: &mut Read<'_, impl GameMemory>,
+        loads: &mut Loads,
+        control: &mut impl TimerControl,
+    ) -> Splitter {
+        if settings.use_igt {
+            let igt = read.igt();
+            if igt.changed() {
+                // game time only moves with the game's own counter
+                control.pause_game_time();
+                control.set_game_time(Duration::seconds(i64::from(igt.current)));
+            }
+        } else if settings.remove_loads {
             let loading = read.loading();
             if loading.changed() {
                 if loading.is_loading() {
-                    timer::pause_game_time();
+                    control.pause_game_time();
+                    loads.start();
                 } else if loading.old.is_loading() {
-                    timer::resume_game_time();
+                    control.resume_game_time();
+                    loads.end(control);
                 }
             }
         }
@@ -1200,7 +918,7 @@ impl Running {
         if settings.count_encounters {
             let encounters = read.encounter_count();
             if encounters.changed() {
-                timer::set_variable_int("encounter_count", encounters.current);
+                control.set_variable_int("encounter_count", encounters.current);
             }
         }
 
@@ -1209,20 +927,21 @@ impl Running {
         }
 
         let level = *read.level();
-        level.split(level.old, read)?;
+        level.split(level.old, settings, custom, read)?;
 
         let battle_state = *read.battle_state();
 
         if battle_state.changed() {
-            read.story_progression().split_battle(battle_state, read)?;
+            read.story_progression()
+                .split_battle(battle_state, settings, custom, read)?;
         }
 
         let story_progress = *read.story_progression();
-        story_progress.split_advance(story_progress.old, read)?;
+        story_progress.split_advance(story_progress.old, settings, custom, read)?;
 
         let yu_yevon = read.yu_yevon();
         if yu_yevon.changed_to(&1) {
-            story_progress.split_yu_yevon(read)?;
+            story_progress.split_yu_yevon(settings, custom, read)?;
         }
 
         if settings.reset {
@@ -1265,7 +984,34 @@ impl Running {
         return NO_SPLIT;
     }
 
-    fn try_split(&mut self, settings: &Settings, split: Splits) -> Option<Splits> {
+    /// Mark the splits that are behind the story progression as seen, once the
+    /// game is loaded. Returns `false` while there is no loaded game yet.
+    fn rebuild_splits(splits: &mut SeenSplits, read: &mut Read<'_, impl GameMemory>) -> bool {
+        if Self::game_loaded(read) == false {
+            return false;
+        }
+
+        let story = read.story_progression().current;
+        for split in split_table::splits_behind(story.0) {
+            if splits.insert(&split) {
+                log!("Already behind split {:?} at story {:?}", split, story);
+            }
+        }
+        return true;
+    }
+
+    /// Whether a save is loaded and the game is not loading.
+    fn game_loaded(read: &mut Read<'_, impl GameMemory>) -> bool {
+        let level = read.level().current;
+        return Level::in_game(level.0) && read.loading().is_loading() == false;
+    }
+
+    fn try_split(
+        &mut self,
+        settings: &Settings,
+        split: Splits,
+        control: &mut impl TimerControl,
+    ) -> Option<Splits> {
         log!("Potential split: {:?}", split);
 
         #[cfg(testing)]
@@ -1290,11 +1036,36 @@ impl Running {
             }
         }
 
-        if self.splits.insert(&split) == false {
+        if self.splits.contains(&split) {
             log!("Ignoring duplicated split: {:?}", split);
             return None;
         }
 
+        if settings.split_order.allows(settings, split, control) == false {
+            return None;
+        }
+
+        self.splits.insert(&split);
+        let keeps_story = split
+            .triggers()
+            .iter()
+            .copied()
+            .chain(
+                settings
+                    .custom_triggers(&self.custom)
+                    .filter(|(custom, _)| *custom == split)
+                    .map(|(_, trigger)| trigger),
+            )
+            .all(Trigger::keeps_story);
+        self.undo.record(split, keeps_story);
+        self.encounters.split(settings, split, control);
+        self.summary.split(
+            split,
+            self.battle_times.count(),
+            self.loads.removed(),
+            control,
+        );
+
         return Some(split);
     }
 
@@ -1378,12 +1149,20 @@ enum UseSplit {
 
 fn find_process() -> Option<BaseAddress> {
     let process = Process::attach("FFX.exe")?;
-    let start = find_entry_point(&process)?;
-    log!("Found main module at {}", start);
-    return Some(BaseAddress { process, start });
+    let Some((start, version)) = find_entry_point(&process) else {
+        AsrTimer.set_variable("game_version", "unsupported");
+        return None;
+    };
+    log!("Found main module at {} for version {:?}", start, version);
+    AsrTimer.set_variable("game_version", version.name());
+    return Some(BaseAddress {
+        process,
+        start,
+        version,
+    });
 }
 
-fn find_entry_point(process: &Process) -> Option<Address> {
+fn find_entry_point(process: &Process) -> Option<(Address, GameVersion)> {
     let main_module = process.get_module_range("FFX.exe").ok()?;
     let sig = Signature::<8>::new("58 0E 00 00 E9 00 00 00");
 
@@ -1398,8 +1177,9 @@ fn find_entry_point(process: &Process) -> Option<Address> {
                 module.1
             );
             let entry_point = entry_point.value().saturating_sub(module.0.value());
-            if entry_point == 0x5493c8 {
-                return Some(module.0);
+            match GameVersion::from_entry_point(entry_point) {
+                Some(version) => return Some((module.0, version)),
+                None => log!("Entry point at {:#X} is not a known version", entry_point),
             }
         }
     }
@@ -1410,6 +1190,7 @@ fn find_entry_point(process: &Process) -> Option<Address> {
 struct BaseAddress {
     process: Process,
     start: Address,
+    version: GameVersion,
 }
 
 type Splitter = ControlFlow<Splits, Action>;
@@ -1473,17 +1254,13 @@ impl Level {
     const DJOSE_PILGRIMAGE_ROAD: u32 = 76;
     const KILIKA_TEMPLE: u32 = 78;
     const MUSHROOM_ROCK_ROAD: u32 = 79;
-    #[cfg(testing)]
     const MACALNIA_ANTECHAMBER: u32 = 80;
     const DJOSE_OUTSIDE: u32 = 82;
-    #[cfg(testing)]
     const LUCA_DOCK_1: u32 = 85;
     const LUCA_DOCK_5: u32 = 89;
     const DJOSE_FAYTH: u32 = 90;
     const DJOSE_HIGHROAD: u32 = 93;
-    #[cfg(testing)]
     const MOONFLOW_NORTH: u32 = 97;
-    #[cfg(testing)]
     const MACALNIA_LAKE: u32 = 102;
     const MOONFLOW_SOUTH_BANK: u32 = 105;
     const MACALANIA_TEMPLE: u32 = 106;
@@ -1497,13 +1274,9 @@ impl Level {
     const THUNDERPLAINS_SOUTH: u32 = 140;
     const MACALANIA_TEMPLE_ROAD: u32 = 153;
     const THUNDERPLAINS_NORTH: u32 = 162;
-    #[cfg(testing)]
     const MACALNIA_LAKE_SHOP: u32 = 164;
-    #[cfg(testing)]
     const CREVASSE: u32 = 192;
-    #[cfg(testing)]
     const HIGHBRIDGE: u32 = 208;
-    #[cfg(testing)]
     const VIA_UNDERWATER: u32 = 209;
     const STADIUM_POOL: u32 = 212;
     const HOME_ENVIRONMENT_CONTROLS: u32 = 219;
@@ -1511,10 +1284,8 @@ impl Level {
     const ZANARKAND_ROAD: u32 = 222;
     const CALM_LANDS: u32 = 223;
     const BEVELLE_ANTECHAMBER: u32 = 226;
-    #[cfg(testing)]
     const MACALNIA_HALLWAY: u32 = 239;
     const MACALANIA_WOODS_NORTH: u32 = 242;
-    #[cfg(testing)]
     const MACALANIA_SPHERIMORPH: u32 = 248;
     const STADIUM_STANDS: u32 = 250;
     const CALM_LANDS_BRIDGE: u32 = 279;
@@ -1524,66 +1295,119 @@ impl Level {
     const NUCLEUS: u32 = 324;
     const DREAMS_END: u32 = 325;
 
+    /// The name of every known level, by id.
+    /// Any other level has no name and is shown by its id.
+    const NAMES: &[(u32, &str)] = &[
+        (Self::BESAID_VILLAGE, "Besaid Village"),
+        (Self::KILIKA_WOODS, "Kilika Woods"),
+        (Self::BESAID_BEACH, "Besaid Beach"),
+        (Self::BESAID_END_ROAD, "Besaid End Road"),
+        (Self::NEW_GAME, "New Game"),
+        (Self::LAGOON, "Lagoon"),
+        (Self::KILIKA_FAYTH, "Kilika Fayth"),
+        (Self::KILIKA_RESIDENTIAL_AREA, "Kilika Residential Area"),
+        (Self::HIGHROAD_AGENCY, "Highroad Agency"),
+        (Self::HIGHROAD_NORTH_END, "Highroad North End"),
+        (Self::BESAID_PROMONTORY, "Besaid Promontory"),
+        (Self::BESAID_VILLAGE_ROAD, "Besaid Village Road"),
+        (Self::MOONFLOW_SOUTH_BANK_ROAD, "Moonflow South Bank Road"),
+        (Self::DJOSE_PILGRIMAGE_ROAD, "Djose Pilgrimage Road"),
+        (Self::KILIKA_TEMPLE, "Kilika Temple"),
+        (Self::MUSHROOM_ROCK_ROAD, "Mushroom Rock Road"),
+        (Self::MACALNIA_ANTECHAMBER, "Macalania Antechamber"),
+        (Self::DJOSE_OUTSIDE, "Djose Outside"),
+        (Self::LUCA_DOCK_1, "Luca Dock 1"),
+        (Self::LUCA_DOCK_5, "Luca Dock 5"),
+        (Self::DJOSE_FAYTH, "Djose Fayth"),
+        (Self::DJOSE_HIGHROAD, "Djose Highroad"),
+        (Self::MOONFLOW_NORTH, "Moonflow North"),
+        (Self::MACALNIA_LAKE, "Macalania Lake"),
+        (Self::MOONFLOW_SOUTH_BANK, "Moonflow South Bank"),
+        (Self::MACALANIA_TEMPLE, "Macalania Temple"),
+        (Self::MACALANIA_WOODS_SOUTH, "Macalania Woods South"),
+        (Self::LUCA_MAIN, "Luca Main"),
+        (Self::HIGHROAD_CENTRAL, "Highroad Central"),
+        (Self::HOME_ENTRANCE, "Home Entrance"),
+        (Self::MUSHROOM_ROCK_AFTERMATH, "Mushroom Rock Aftermath"),
+        (Self::GUADOSALAM, "Guadosalam"),
+        (Self::BIKANEL_NORTH, "Bikanel North"),
+        (Self::THUNDERPLAINS_SOUTH, "Thunder Plains South"),
+        (Self::MACALANIA_TEMPLE_ROAD, "Macalania Temple Road"),
+        (Self::THUNDERPLAINS_NORTH, "Thunder Plains North"),
+        (Self::MACALNIA_LAKE_SHOP, "Macalania Lake Shop"),
+        (Self::CREVASSE, "Crevasse"),
+        (Self::HIGHBRIDGE, "Highbridge"),
+        (Self::VIA_UNDERWATER, "Via Underwater"),
+        (Self::STADIUM_POOL, "Stadium Pool"),
+        (Self::HOME_ENVIRONMENT_CONTROLS, "Home Environment Controls"),
+        (Self::MACALANIA_SPRING, "Macalania Spring"),
+        (Self::ZANARKAND_ROAD, "Zanarkand Road"),
+        (Self::CALM_LANDS, "Calm Lands"),
+        (Self::BEVELLE_ANTECHAMBER, "Bevelle Antechamber"),
+        (Self::MACALNIA_HALLWAY, "Macalania Hallway"),
+        (Self::MACALANIA_WOODS_NORTH, "Macalania Woods North"),
+        (Self::MACALANIA_SPHERIMORPH, "Macalania Spherimorph"),
+        (Self::STADIUM_STANDS, "Stadium Stands"),
+        (Self::CALM_LANDS_BRIDGE, "Calm Lands Bridge"),
+        (Self::HOME_MAIN_CORRIDOR, "Home Main Corridor"),
+        (Self::BEVELLE_TRIALS, "Bevelle Trials"),
+        (Self::ZANARKAND_DOME, "Zanarkand Dome"),
+        (Self::NUCLEUS, "Nucleus"),
+        (Self::DREAMS_END, "Dream's End"),
+    ];
+
+    fn name(self) -> Option<&'static str> {
+        return Self::NAMES
+            .iter()
+            .find_map(|&(id, name)| (id == self.0).then_some(name));
+    }
+
+    /// Publish the name of the level as the `zone` variable when it changes.
+    fn update_zone(read: &mut Read<'_, impl GameMemory>, control: &mut impl TimerControl) {
+        let level = read.level().current;
+        if read.level().changed() == false {
+            return;
+        }
+        match level.name() {
+            Some(name) => control.set_variable("zone", name),
+            None => control.set_variable("zone", &format!("Level {}", level.0)),
+        }
+    }
+
     fn new_game(self) -> bool {
         self.0 == Self::NEW_GAME
     }
 
-    fn split(self, old: Self, read: &mut Read<'_>) -> Splitter {
+    fn split(
+        self,
+        old: Self,
+        settings: &Settings,
+        custom: &custom_splits::Rules,
+        read: &mut Read<'_, impl GameMemory>,
+    ) -> Splitter {
         if self == old {
             return NO_SPLIT;
         }
-        ControlFlow::Break(match (old.0, self.0) {
-            (Self::LAGOON, Self::BESAID_PROMONTORY | Self::BESAID_VILLAGE_ROAD) => Splits::Lagoon, // story 119->124
-            (Self::BESAID_VILLAGE, Self::BESAID_VILLAGE_ROAD) => Splits::BesaidVillage, // story == 200
-            (Self::BESAID_END_ROAD, Self::BESAID_BEACH) => Splits::BesaidRoad, // story == 217
-            (Self::KILIKA_FAYTH, Self::KILIKA_TEMPLE) => Splits::Ifrit,        // story 346 -> 348
-            (Self::KILIKA_WOODS, Self::KILIKA_RESIDENTIAL_AREA) => Splits::Kilika,
-            #[cfg(testing)]
-            (Self::LUCA_MAIN, Self::LUCA_DOCK_1) if read.is_at(Progress::WORKERS) => Splits::Luca,
-            (Self::LUCA_DOCK_5, Self::LUCA_MAIN) if read.is_at(Progress::BLITZBALL) => {
-                Splits::BeforeBlitzball
-            }
-            (Self::STADIUM_POOL, Self::STADIUM_STANDS) => Splits::BlitzballDone,
-            (Self::HIGHROAD_CENTRAL, Self::HIGHROAD_AGENCY) => Splits::MiihenRoad,
-            (Self::HIGHROAD_NORTH_END, Self::MUSHROOM_ROCK_ROAD) => Splits::OldRoad,
-            (Self::MUSHROOM_ROCK_ROAD, Self::MUSHROOM_ROCK_AFTERMATH) => Splits::MrrSkip,
-            (Self::DJOSE_HIGHROAD, Self::DJOSE_PILGRIMAGE_ROAD) => Splits::DjoseRoad,
-            (Self::DJOSE_FAYTH, Self::DJOSE_OUTSIDE) => Splits::Ixion, // story == 998
-            (Self::DJOSE_HIGHROAD, Self::MOONFLOW_SOUTH_BANK_ROAD) => Splits::EnterMoonflow, // story == 1030
-            (Self::MOONFLOW_SOUTH_BANK_ROAD, Self::MOONFLOW_SOUTH_BANK) => Splits::MoonflowSouth,
-            #[cfg(testing)]
-            (Self::MOONFLOW_NORTH, Self::GUADOSALAM) => Splits::MoonflowNorth,
-            (Self::GUADOSALAM, Self::THUNDERPLAINS_SOUTH) => Splits::Guadosalam,
-            (Self::THUNDERPLAINS_NORTH, Self::MACALANIA_WOODS_SOUTH) => Splits::ThunderPlains,
-            (Self::MACALANIA_WOODS_NORTH, Self::MACALANIA_SPRING) => Splits::MacalaniaWoods, // story == 1413
-            #[cfg(testing)]
-            (Self::MACALANIA_SPRING, Self::MACALANIA_SPHERIMORPH) => Splits::OakaShop,
-            #[cfg(testing)]
-            (Self::MACALANIA_SPRING, Self::MACALNIA_LAKE_SHOP) => Splits::CrawlerGrid, // story == 1470
-            #[cfg(testing)]
-            (Self::MACALANIA_TEMPLE_ROAD, Self::MACALANIA_TEMPLE) => Splits::SeymourGrid, // story == 1504
-            #[cfg(testing)]
-            (Self::MACALNIA_ANTECHAMBER, Self::MACALNIA_HALLWAY) => Splits::WendigoGrid,
-            (Self::MACALANIA_TEMPLE, Self::MACALANIA_TEMPLE_ROAD) => Splits::Shiva, // story == 1557
-            #[cfg(testing)]
-            (Self::CREVASSE, Self::MACALNIA_LAKE) => Splits::Crevasse,
-            (Self::BIKANEL_NORTH, Self::HOME_ENTRANCE) => Splits::Bikanel, // story 1720 -> 1800
-            (Self::HOME_MAIN_CORRIDOR, Self::HOME_ENVIRONMENT_CONTROLS) => Splits::Home, // story = 1940
-            (Self::BEVELLE_TRIALS, Self::BEVELLE_ANTECHAMBER) => Splits::Bahamut,
-            #[cfg(testing)]
-            (Self::VIA_UNDERWATER, Self::HIGHBRIDGE) => Splits::ViaUnderwater, // story = 2220,
-            (Self::CALM_LANDS, Self::CALM_LANDS_BRIDGE) => Splits::CalmLands, // story = 2400
-            (Self::ZANARKAND_ROAD, Self::ZANARKAND_DOME) => Splits::Zanarkand, // stgory = 2767
-            (Self::NUCLEUS, Self::DREAMS_END) => Splits::Eggs,                // story == 3260
-            #[cfg(testing)]
-            (from, to) if from != to && Self::in_game(from) && Self::in_game(to) => {
-                Splits::LevelSplit
+
+        split_table::find_split(settings, custom, |trigger| match trigger {
+            Trigger::Level { from, to, story } => {
+                // any level is only one that was actually played in,
+                // not the empty level before attaching or loading a save
+                from.map_or(Self::in_game(old.0), |from| old.0 == from)
+                    && to.is_none_or(|to| self.0 == to)
+                    && story.is_none_or(|story| read.is_at(story))
             }
-            _ => return NO_SPLIT,
-        })
+            _ => false,
+        })?;
+
+        #[cfg(testing)]
+        if Self::in_game(old.0) && Self::in_game(self.0) {
+            return ControlFlow::Break(Splits::LevelSplit);
+        }
+
+        return NO_SPLIT;
     }
 
-    #[cfg(testing)]
     fn in_game(level: u32) -> bool {
         return level > 0 && level != Level::NEW_GAME;
     }
@@ -1631,158 +1455,134 @@ impl Progress {
     const KLIKK: u32 = 55;
     const TROS: u32 = 76;
     const LAGOON: u32 = 119;
+    const BESAID_VILLAGE: u32 = 200;
     const KIMAHRI: u32 = 214;
-    #[cfg(testing)]
+    const BESAID_ROAD: u32 = 217;
     const SINFIN: u32 = 272;
     const ECHUILLES: u32 = 280;
     const GENEAUX: u32 = 322;
-    #[cfg(testing)]
     const WORKERS: u32 = 492;
     const OBLITZERATOR: u32 = 502;
     const BLITZBALL: u32 = 514;
-    #[cfg(testing)]
     const SAHAGINS: u32 = 583;
     const GARUDA: u32 = 600;
     const CHOCOBO_EATER: u32 = 770;
     const MRR: u32 = 835;
     const GUI: u32 = 865;
+    const IXION: u32 = 998;
+    const ENTER_MOONFLOW: u32 = 1030;
     const EXTRACTOR: u32 = 1060;
+    const MACALANIA_WOODS: u32 = 1413;
     const SPHERIMORPH: u32 = 1420;
+    const CRAWLER_GRID: u32 = 1470;
     const CRAWLER: u32 = 1485;
+    const SEYMOUR_GRID: u32 = 1504;
     const SEYMOUR: u32 = 1540;
+    const SHIVA: u32 = 1557;
     const WENDIGO: u32 = 1570;
-    #[cfg(testing)]
     const BIKANEL_KIMAHRI: u32 = 1718;
-    #[cfg(testing)]
     const BIKANEL_RIKKU: u32 = 1720;
-    #[cfg(testing)]
     const HOME: u32 = 1820;
-    #[cfg(testing)]
     const HOME2: u32 = 1885;
+    const HOME_CONTROLS: u32 = 1940;
     const EVRAE: u32 = 2040;
     const GUARDS: u32 = 2080;
     const ISAARU: u32 = 2220;
+    const VIA_UNDERWATER: u32 = 2220;
     const NATUS: u32 = 2280;
-    #[cfg(testing)]
     const DEFENDER: u32 = 2400;
+    const CALM_LANDS: u32 = 2400;
     const BIRAN_YENKE: u32 = 2510;
     const FLUX: u32 = 2530;
     const SANCTUARY_KEEPER: u32 = 2585;
+    const ZANARKAND: u32 = 2767;
     const TETRIS: u32 = 2767;
     const SPECTRAL_KEEPER: u32 = 2775;
     const YUNALESCA: u32 = 2815;
     const SIN_CORE: u32 = 3105;
     const OVERDRIVE_SIN: u32 = 3135;
     const OMNIS: u32 = 3205;
+    const EGGS: u32 = 3260;
     const BFA: u32 = 3300;
     const YU_YEVON: u32 = 3380;
 
-    fn split_battle(self, battle_state: Pair<BattleState>, read: &mut Read<'_>) -> Splitter {
-        if battle_state.is_over() == false {
-            return NO_SPLIT;
-        }
+    #[cfg(not(target_family = "wasm"))]
+    fn name(self) -> Option<&'static str> {
+        return StoryChoice::find(self.0).map(StoryChoice::name);
+    }
 
-        if battle_state.escaped() && self.0 != Self::CHOCOBO_EATER {
+    fn split_battle(
+        self,
+        battle_state: Pair<BattleState>,
+        settings: &Settings,
+        custom: &custom_splits::Rules,
+        read: &mut Read<'_, impl GameMemory>,
+    ) -> Splitter {
+        if battle_state.is_over() == false {
             return NO_SPLIT;
         }
 
-        let mut is_encounter = |map_id, id1, id2| -> bool {
-            return Self::is_encounter(read, map_id, id1, id2);
-        };
-
-        ControlFlow::Break(match self.0 {
-            Self::AMMES => Splits::Ammes,
-            Self::KLIKK if battle_state.fanfare() => Splits::Klikk,
-            Self::TROS if is_encounter(4, 0, 0) => Splits::Tros,
-            Self::KIMAHRI => Splits::Kimahri,
-            #[cfg(testing)]
-            Self::SINFIN => Splits::SinFin,
-            Self::ECHUILLES => Splits::Echuilles,
-            Self::GENEAUX => Splits::Geneaux,
-            #[cfg(testing)]
-            Self::WORKERS if is_encounter(12, 0, 0) => Splits::Workers1,
-            #[cfg(testing)]
-            Self::WORKERS if is_encounter(13, 0, 0) => Splits::Workers2,
-            #[cfg(testing)]
-            Self::WORKERS if is_encounter(14, 0, 0) => Splits::Workers3,
-            Self::OBLITZERATOR => Splits::Oblitzerator,
-            #[cfg(testing)]
-            Self::SAHAGINS => Splits::Sahagins,
-            Self::GARUDA if is_encounter(17, 0, 1) => Splits::Garuda,
-            Self::CHOCOBO_EATER => Splits::ChocoboEater,
-            Self::GUI => Splits::Gui,
-            Self::EXTRACTOR => Splits::Extractor,
-            Self::SPHERIMORPH => Splits::Spherimorph,
-            Self::CRAWLER => Splits::Crawler,
-            Self::SEYMOUR => Splits::Seymour,
-            Self::WENDIGO if is_encounter(44, 0, 1) => Splits::Wendigo,
-            #[cfg(testing)]
-            Self::BIKANEL_RIKKU if is_encounter(48, 2, 0) => Splits::BikanelYeet,
-            #[cfg(testing)]
-            Self::HOME if is_encounter(87, 0, 0) => Splits::Bombs,
-            #[cfg(testing)]
-            Self::HOME if is_encounter(87, 0, 2) => Splits::DualHorns,
-            #[cfg(testing)]
-            Self::HOME2 if is_encounter(87, 0, 3) => Splits::Chimeras,
-            Self::EVRAE => Splits::Evrae,
-            #[cfg(testing)]
-            Self::GUARDS if is_encounter(53, 0, 0) => Splits::Guards1, // story = 2080
-            #[cfg(testing)]
-            Self::GUARDS if is_encounter(53, 0, 1) => Splits::Guards2,
-            Self::GUARDS if is_encounter(53, 0, 2) => Splits::Guards,
-            Self::ISAARU if is_encounter(54, 2, 2) => Splits::Isaaru,
-            #[cfg(testing)]
-            Self::ISAARU if is_encounter(55, 1, 0) => Splits::Altana,
-            Self::NATUS => Splits::Natus,
-            #[cfg(testing)]
-            Self::DEFENDER if is_encounter(61, 0, 0) => Splits::Defender,
-            Self::BIRAN_YENKE if battle_state.fanfare() => Splits::BiranYenke,
-            Self::FLUX if is_encounter(65, 0, 0) => Splits::Flux,
-            Self::SANCTUARY_KEEPER if is_encounter(68, 0, 0) => Splits::SanctuaryKeeper,
-            Self::SPECTRAL_KEEPER => Splits::SpectralKeeper,
-            Self::YUNALESCA => Splits::Yunalesca,
-            Self::SIN_CORE => Splits::Core,
-            Self::OVERDRIVE_SIN => Splits::Overdrive,
-            Self::OMNIS if is_encounter(78, 0, 0) => Splits::Omnis,
-            Self::BFA => Splits::Bfa,
-            _ => return NO_SPLIT,
-        })
+        return split_table::find_split(settings, custom, |trigger| match trigger {
+            Trigger::Battle {
+                story,
+                encounter,
+                fanfare,
+                escape,
+            } => {
+                self.0 == story
+                    && (battle_state.escaped() == false || escape)
+                    && (fanfare == false || battle_state.fanfare())
+                    && encounter.is_none_or(|encounter| encounter.matches(read))
+            }
+            _ => false,
+        });
     }
 
-    fn split_advance(self, old: Self, read: &mut Read<'_>) -> Splitter {
-        if self.0 == Self::ISAARU {
-            if read.cutscene_type().changed_from_to(&18, &257) {
-                if Self::is_encounter(read, 54, 1, 0) {
-                    return ControlFlow::Break(Splits::ViaPurifico);
-                }
+    fn split_advance(
+        self,
+        old: Self,
+        settings: &Settings,
+        custom: &custom_splits::Rules,
+        read: &mut Read<'_, impl GameMemory>,
+    ) -> Splitter {
+        split_table::find_split(settings, custom, |trigger| match trigger {
+            Trigger::Cutscene {
+                story,
+                from,
+                to,
+                encounter,
+            } => {
+                self.0 == story
+                    && read.cutscene_type().changed_from_to(&from, &to)
+                    && encounter.is_none_or(|encounter| encounter.matches(read))
             }
-        }
+            _ => false,
+        })?;
 
         if self <= old {
             return NO_SPLIT;
         }
 
-        ControlFlow::Break(match old.0 {
-            Self::LAGOON if read.cutscene_type().either(73) => Splits::Lagoon,
-            Self::MRR if read.cutscene_type().either(940) => Splits::Mrr,
-            #[cfg(testing)]
-            Self::BIKANEL_KIMAHRI if self.0 == Self::BIKANEL_RIKKU => Splits::BikanelParty,
-            Self::TETRIS if self.0 == Self::SPECTRAL_KEEPER => Splits::Tetris,
-            _ => return NO_SPLIT,
-        })
-    }
-
-    fn split_yu_yevon(self, read: &mut Read<'_>) -> Splitter {
-        if self.0 == Self::YU_YEVON {
-            if read.hp_enemy_a().current == 0 {
-                return ControlFlow::Break(Splits::YuYevon);
+        return split_table::find_split(settings, custom, |trigger| match trigger {
+            Trigger::Advance { from, to, cutscene } => {
+                old.0 == from
+                    && to.is_none_or(|to| self.0 == to)
+                    && cutscene.is_none_or(|cutscene| read.cutscene_type().either(cutscene))
             }
-        }
-        NO_SPLIT
+            _ => false,
+        });
     }
 
-    fn is_encounter(read: &mut Read<'_>, map_id: u16, id1: u8, id2: u8) -> bool {
-        return read.map_id().current == map_id && read.formation_id().is(id1, id2);
+    fn split_yu_yevon(
+        self,
+        settings: &Settings,
+        custom: &custom_splits::Rules,
+        read: &mut Read<'_, impl GameMemory>,
+    ) -> Splitter {
+        return split_table::find_split(settings, custom, |trigger| match trigger {
+            Trigger::HpZero { story } => self.0 == story && read.hp_enemy_a().current == 0,
+            _ => false,
+        });
     }
 
     fn is(self, progress: u32) -> bool {
@@ -1790,7 +1590,11 @@ impl Progress {
     }
 
     #[cfg(testing)]
-    fn split_workers(self, battle_state: Pair<BattleState>, read: &mut Read<'_>) -> Splitter {
+    fn split_workers(
+        self,
+        battle_state: Pair<BattleState>,
+        read: &mut Read<'_, impl GameMemory>,
+    ) -> Splitter {
         if self.0 == Self::WORKERS
             && battle_state.is_over() == false
             && read.map_id().current == 14
@@ -1805,7 +1609,11 @@ impl Progress {
     }
 
     #[cfg(testing)]
-    fn split_sahagins(self, battle_state: Pair<BattleState>, read: &mut Read<'_>) -> Splitter {
+    fn split_sahagins(
+        self,
+        battle_state: Pair<BattleState>,
+        read: &mut Read<'_, impl GameMemory>,
+    ) -> Splitter {
         if self.0 == Self::SAHAGINS
             && battle_state.is_over() == false
             && read.map_id().current == 16
@@ -1829,9 +1637,13 @@ impl Progress {
 struct Formation(u16);
 
 impl Formation {
+    const fn new(id1: u8, id2: u8) -> Self {
+        return Self(u16::from_ne_bytes([id1, id2]));
+    }
+
+    #[cfg(testing)]
     const fn is(self, id1: u8, id2: u8) -> bool {
-        let value = u16::from_ne_bytes([id1, id2]);
-        return self.0 == value;
+        return self.0 == Self::new(id1, id2).0;
     }
 }
 
@@ -1839,9 +1651,14 @@ impl Formation {
 #[repr(transparent)]
 struct Hp(u32);
 
+impl Hp {
+    /// The HP of an enemy slot that could not be read.
+    const UNKNOWN: u32 = u32::MAX;
+}
+
 impl Default for Hp {
     fn default() -> Self {
-        Self(u32::MAX)
+        Self(Self::UNKNOWN)
     }
 }
 
@@ -1861,42 +1678,35 @@ struct Memory {
     select_screen: DeepPointer<1>,
     #[cfg(testing)]
     loading_slot: DeepPointer<1>,
-    #[cfg(testing)]
     hp_enemy_b: DeepPointer<2>,
-    #[cfg(testing)]
     hp_enemy_c: DeepPointer<2>,
-    #[cfg(testing)]
     hp_enemy_d: DeepPointer<2>,
-    #[cfg(testing)]
     igt: DeepPointer<1>,
 }
 
 impl Memory {
     fn new(base: &BaseAddress) -> Memory {
+        let offsets = base.version.offsets();
         return Memory {
-            is_loading: DeepPointer::new_32bit(base.start, &[0x8CC898, 0x123A4]),
-            encounter_counter: DeepPointer::new_32bit(base.start, &[0xD307A4]),
-            current_level: DeepPointer::new_32bit(base.start, &[0x8CB990]),
-            story_progression: DeepPointer::new_32bit(base.start, &[0x84949C]),
-            battle_state: DeepPointer::new_32bit(base.start, &[0x390D90, 0x4]),
-            cutscene_type: DeepPointer::new_32bit(base.start, &[0xD27C88]),
-            map_id: DeepPointer::new_32bit(base.start, &[0xD2C256]),
-            formation_id: DeepPointer::new_32bit(base.start, &[0xD2C258]),
-            yu_yevon: DeepPointer::new_32bit(base.start, &[0xD2A8E8]),
-            hp_enemy_a: DeepPointer::new_32bit(base.start, &[0xD34460, 0x5D0]),
-            cursor_position: DeepPointer::new_32bit(base.start, &[0x1467808]),
-            input: DeepPointer::new_32bit(base.start, &[0x8CB170]),
-            select_screen: DeepPointer::new_32bit(base.start, &[0xF25B30]),
-            #[cfg(testing)]
-            loading_slot: DeepPointer::new_32bit(base.start, &[0x8E72DC]),
-            #[cfg(testing)]
-            hp_enemy_b: DeepPointer::new_32bit(base.start, &[0xD34460, 0x1560]),
-            #[cfg(testing)]
-            hp_enemy_c: DeepPointer::new_32bit(base.start, &[0xD34460, 0x24F0]),
-            #[cfg(testing)]
-            hp_enemy_d: DeepPointer::new_32bit(base.start, &[0xD34460, 0x3480]),
-            #[cfg(testing)]
-            igt: DeepPointer::new_32bit(base.start, &[0xD2CB4C]),
+            is_loading: DeepPointer::new_32bit(base.start, &offsets.is_loading),
+            encounter_counter: DeepPointer::new_32bit(base.start, &offsets.encounter_counter),
+            current_level: DeepPointer::new_32bit(base.start, &offsets.current_level),
+            story_progression: DeepPointer::new_32bit(base.start, &offsets.story_progression),
+            battle_state: DeepPointer::new_32bit(base.start, &offsets.battle_state),
+            cutscene_type: DeepPointer::new_32bit(base.start, &offsets.cutscene_type),
+            map_id: DeepPointer::new_32bit(base.start, &offsets.map_id),
+            formation_id: DeepPointer::new_32bit(base.start, &offsets.formation_id),
+            yu_yevon: DeepPointer::new_32bit(base.start, &offsets.yu_yevon),
+            hp_enemy_a: DeepPointer::new_32bit(base.start, &offsets.hp_enemy_a),
+            cursor_position: DeepPointer::new_32bit(base.start, &offsets.cursor_position),
+            input: DeepPointer::new_32bit(base.start, &offsets.input),
+            select_screen: DeepPointer::new_32bit(base.start, &offsets.select_screen),
+            #[cfg(testing)]
+            loading_slot: DeepPointer::new_32bit(base.start, &offsets.loading_slot),
+            hp_enemy_b: DeepPointer::new_32bit(base.start, &offsets.hp_enemy_b),
+            hp_enemy_c: DeepPointer::new_32bit(base.start, &offsets.hp_enemy_c),
+            hp_enemy_d: DeepPointer::new_32bit(base.start, &offsets.hp_enemy_d),
+            igt: DeepPointer::new_32bit(base.start, &offsets.igt),
         };
     }
 }
@@ -1916,11 +1726,11 @@ impl<T> Watch<T> {
         })
     }
 
-    fn update<const N: usize>(&mut self, process: &Process, memory: &DeepPointer<N>) -> &Pair<T>
+    fn update(&mut self, game: &impl GameMemory, pointer: Pointer) -> &Pair<T>
     where
         T: Clone + CheckedBitPattern,
     {
-        if let Ok(value) = memory.deref(process) {
+        if let Some(value) = game.read(pointer) {
             return self.0.update_infallible(value);
         } else {
             return self.0.pair.as_ref().unwrap();
@@ -1944,13 +1754,9 @@ struct Watchers {
     input: Watch<Input>,
     #[cfg(testing)]
     loading_slot: Watch<u64>,
-    #[cfg(testing)]
     hp_enemy_b: Watch<u32>,
-    #[cfg(testing)]
     hp_enemy_c: Watch<u32>,
-    #[cfg(testing)]
     hp_enemy_d: Watch<u32>,
-    #[cfg(testing)]
     igt: Watch<u32>,
 }
 
@@ -1972,116 +1778,102 @@ impl Watchers {
             input: Watch::new(),
             #[cfg(testing)]
             loading_slot: Watch::new(),
-            #[cfg(testing)]
             hp_enemy_b: Watch::new(),
-            #[cfg(testing)]
             hp_enemy_c: Watch::new(),
-            #[cfg(testing)]
             hp_enemy_d: Watch::new(),
-            #[cfg(testing)]
             igt: Watch::new(),
         };
     }
 
-    fn loading(&mut self, process: &Process, memory: &Memory) -> &Pair<Loading> {
-        return self.is_loading.update(process, &memory.is_loading);
+    fn loading(&mut self, game: &impl GameMemory) -> &Pair<Loading> {
+        return self.is_loading.update(game, Pointer::Loading);
     }
 
-    fn encounter_count(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        return self
-            .encounter_counter
-            .update(process, &memory.encounter_counter);
+    fn encounter_count(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        return self.encounter_counter.update(game, Pointer::EncounterCount);
     }
 
-    fn level(&mut self, process: &Process, memory: &Memory) -> &Pair<Level> {
-        return self.current_level.update(process, &memory.current_level);
+    fn level(&mut self, game: &impl GameMemory) -> &Pair<Level> {
+        return self.current_level.update(game, Pointer::Level);
     }
 
-    fn story_progression(&mut self, process: &Process, memory: &Memory) -> &Pair<Progress> {
+    fn story_progression(&mut self, game: &impl GameMemory) -> &Pair<Progress> {
         return self
             .story_progression
-            .update(process, &memory.story_progression);
+            .update(game, Pointer::StoryProgression);
     }
 
-    fn battle_state(&mut self, process: &Process, memory: &Memory) -> &Pair<BattleState> {
-        return self.battle_state.update(process, &memory.battle_state);
+    fn battle_state(&mut self, game: &impl GameMemory) -> &Pair<BattleState> {
+        return self.battle_state.update(game, Pointer::BattleState);
     }
 
-    fn cutscene_type(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        return self.cutscene_type.update(process, &memory.cutscene_type);
+    fn cutscene_type(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        return self.cutscene_type.update(game, Pointer::CutsceneType);
     }
 
-    fn map_id(&mut self, process: &Process, memory: &Memory) -> &Pair<u16> {
-        return self.map_id.update(process, &memory.map_id);
+    fn map_id(&mut self, game: &impl GameMemory) -> &Pair<u16> {
+        return self.map_id.update(game, Pointer::MapId);
     }
 
-    fn formation_id(&mut self, process: &Process, memory: &Memory) -> &Pair<Formation> {
-        return self.formation_id.update(process, &memory.formation_id);
+    fn formation_id(&mut self, game: &impl GameMemory) -> &Pair<Formation> {
+        return self.formation_id.update(game, Pointer::FormationId);
     }
 
-    fn yu_yevon(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        return self.yu_yevon.update(process, &memory.yu_yevon);
+    fn yu_yevon(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        return self.yu_yevon.update(game, Pointer::YuYevon);
     }
 
-    fn hp_enemy_a(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        let value = memory.hp_enemy_a.deref(process).map_or(Hp::default(), Hp);
+    fn hp_enemy_a(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        let value = game.read(Pointer::HpEnemyA).map_or(Hp::default(), Hp);
         return self.hp_enemy_a.0.update_infallible(value.0);
     }
 
-    fn select_screen(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        return self.select_screen.update(process, &memory.select_screen);
+    fn select_screen(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        return self.select_screen.update(game, Pointer::SelectScreen);
     }
 
-    fn cursor_position(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        return self
-            .cursor_position
-            .update(process, &memory.cursor_position);
+    fn cursor_position(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        return self.cursor_position.update(game, Pointer::CursorPosition);
     }
 
-    fn input(&mut self, process: &Process, memory: &Memory) -> &Pair<Input> {
-        return self.input.update(process, &memory.input);
+    fn input(&mut self, game: &impl GameMemory) -> &Pair<Input> {
+        return self.input.update(game, Pointer::Input);
     }
 
     #[cfg(testing)]
-    fn loading_slot(&mut self, process: &Process, memory: &Memory) -> Pair<u32> {
+    fn loading_slot(&mut self, game: &impl GameMemory) -> Pair<u32> {
         return self
             .loading_slot
-            .update(process, &memory.loading_slot)
+            .update(game, Pointer::LoadingSlot)
             .map(|long| {
                 let [idx, off] = checked::cast::<_, [u32; 2]>(long);
                 idx.saturating_add(off)
             });
     }
 
-    #[cfg(testing)]
-    fn hp_enemy_b(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        let value = memory.hp_enemy_b.deref(process).map_or(Hp::default(), Hp);
+    fn hp_enemy_b(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        let value = game.read(Pointer::HpEnemyB).map_or(Hp::default(), Hp);
         return self.hp_enemy_b.0.update_infallible(value.0);
     }
 
-    #[cfg(testing)]
-    fn hp_enemy_c(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        let value = memory.hp_enemy_c.deref(process).map_or(Hp::default(), Hp);
+    fn hp_enemy_c(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        let value = game.read(Pointer::HpEnemyC).map_or(Hp::default(), Hp);
         return self.hp_enemy_c.0.update_infallible(value.0);
     }
 
-    #[cfg(testing)]
-    fn hp_enemy_d(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        let value = memory.hp_enemy_d.deref(process).map_or(Hp::default(), Hp);
+    fn hp_enemy_d(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        let value = game.read(Pointer::HpEnemyD).map_or(Hp::default(), Hp);
         return self.hp_enemy_d.0.update_infallible(value.0);
     }
 
-    #[cfg(testing)]
-    fn igt(&mut self, process: &Process, memory: &Memory) -> &Pair<u32> {
-        let value = memory.igt.deref(process).unwrap_or_default();
-        return self.igt.0.update_infallible(value);
+    fn igt(&mut self, game: &impl GameMemory) -> &Pair<u32> {
+        return self.igt.update(game, Pointer::Igt);
     }
 }
 
-struct Read<'a> {
+struct Read<'a, G> {
     watchers: &'a mut Watchers,
-    process: &'a Process,
-    memory: &'a Memory,
+    game: &'a G,
     is_loading: Option<Pair<Loading>>,
     encounter_counter: Option<Pair<u32>>,
     current_level: Option<Pair<Level>>,
@@ -2097,22 +1889,17 @@ struct Read<'a> {
     input: Option<Pair<Input>>,
     #[cfg(testing)]
     loading_slot: Option<Pair<u32>>,
-    #[cfg(testing)]
     hp_enemy_b: Option<Pair<u32>>,
-    #[cfg(testing)]
     hp_enemy_c: Option<Pair<u32>>,
-    #[cfg(testing)]
     hp_enemy_d: Option<Pair<u32>>,
-    #[cfg(testing)]
     igt: Option<Pair<u32>>,
 }
 
-impl<'a> Read<'a> {
-    fn new(watchers: &'a mut Watchers, process: &'a Process, memory: &'a Memory) -> Self {
+impl<'a, G: GameMemory> Read<'a, G> {
+    fn new(watchers: &'a mut Watchers, game: &'a G) -> Self {
         Self {
             watchers,
-            process,
-            memory,
+            game,
             is_loading: None,
             encounter_counter: None,
             current_level: None,
@@ -2128,80 +1915,86 @@ impl<'a> Read<'a> {
             input: None,
             #[cfg(testing)]
             loading_slot: None,
-            #[cfg(testing)]
             hp_enemy_b: None,
-            #[cfg(testing)]
             hp_enemy_c: None,
-            #[cfg(testing)]
             hp_enemy_d: None,
-            #[cfg(testing)]
             igt: None,
         }
     }
 
     fn loading(&mut self) -> &Pair<Loading> {
         self.is_loading
-            .get_or_insert_with(|| *self.watchers.loading(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.loading(self.game))
     }
 
     fn encounter_count(&mut self) -> &Pair<u32> {
         self.encounter_counter
-            .get_or_insert_with(|| *self.watchers.encounter_count(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.encounter_count(self.game))
     }
 
     fn level(&mut self) -> &Pair<Level> {
         self.current_level
-            .get_or_insert_with(|| *self.watchers.level(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.level(self.game))
     }
 
     fn story_progression(&mut self) -> &Pair<Progress> {
         self.story_progression
-            .get_or_insert_with(|| *self.watchers.story_progression(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.story_progression(self.game))
     }
 
     fn battle_state(&mut self) -> &Pair<BattleState> {
         self.battle_state
-            .get_or_insert_with(|| *self.watchers.battle_state(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.battle_state(self.game))
     }
 
     fn cutscene_type(&mut self) -> &Pair<u32> {
         self.cutscene_type
-            .get_or_insert_with(|| *self.watchers.cutscene_type(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.cutscene_type(self.game))
     }
 
     fn hp_enemy_a(&mut self) -> &Pair<u32> {
         self.hp_enemy_a
-            .get_or_insert_with(|| *self.watchers.hp_enemy_a(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.hp_enemy_a(self.game))
     }
 
     fn yu_yevon(&mut self) -> &Pair<u32> {
         self.yu_yevon
-            .get_or_insert_with(|| *self.watchers.yu_yevon(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.yu_yevon(self.game))
     }
 
     fn map_id(&mut self) -> &Pair<u16> {
         self.map_id
-            .get_or_insert_with(|| *self.watchers.map_id(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.map_id(self.game))
     }
 
     fn formation_id(&mut self) -> &Pair<Formation> {
         self.formation_id
-            .get_or_insert_with(|| *self.watchers.formation_id(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.formation_id(self.game))
     }
 
     fn select_screen(&mut self) -> &Pair<u32> {
         self.select_screen
-            .get_or_insert_with(|| *self.watchers.select_screen(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.select_screen(self.game))
     }
 
     fn cursor_position(&mut self) -> &Pair<u32> {
         self.cursor_position
-            .get_or_insert_with(|| *self.watchers.cursor_position(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.cursor_position(self.game))
     }
 
     fn input(&mut self) -> &Pair<Input> {
         self.input
-            .get_or_insert_with(|| *self.watchers.input(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.input(self.game))
+    }
+
+    /// The HP of every enemy slot, from A to D.
+    fn hp_enemies(&mut self) -> [Pair<u32>; 4] {
+        return [
+            *self.hp_enemy_a(),
+            *self.hp_enemy_b(),
+            *self.hp_enemy_c(),
+            *self.hp_enemy_d(),
+        ];
     }
 
     fn is_at(&mut self, story: u32) -> bool {
@@ -2211,31 +2004,27 @@ impl<'a> Read<'a> {
     #[cfg(testing)]
     fn loading_slot(&mut self) -> &Pair<u32> {
         self.loading_slot
-            .get_or_insert_with(|| self.watchers.loading_slot(self.process, self.memory))
+            .get_or_insert_with(|| self.watchers.loading_slot(self.game))
     }
 
-    #[cfg(testing)]
     fn hp_enemy_b(&mut self) -> &Pair<u32> {
         self.hp_enemy_b
-            .get_or_insert_with(|| *self.watchers.hp_enemy_b(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.hp_enemy_b(self.game))
     }
 
-    #[cfg(testing)]
     fn hp_enemy_c(&mut self) -> &Pair<u32> {
         self.hp_enemy_c
-            .get_or_insert_with(|| *self.watchers.hp_enemy_c(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.hp_enemy_c(self.game))
     }
 
-    #[cfg(testing)]
     fn hp_enemy_d(&mut self) -> &Pair<u32> {
         self.hp_enemy_d
-            .get_or_insert_with(|| *self.watchers.hp_enemy_d(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.hp_enemy_d(self.game))
     }
 
-    #[cfg(testing)]
     fn igt(&mut self) -> &Pair<u32> {
         self.igt
-            .get_or_insert_with(|| *self.watchers.igt(self.process, self.memory))
+            .get_or_insert_with(|| *self.watchers.igt(self.game))
     }
 }
 
@@ -2253,8 +2042,18 @@ impl fmt::Debug for Settings {
             start,
             split,
             reset,
+            undo_splits,
             remove_loads,
+            use_igt,
+            pause_on_crash,
             count_encounters,
+            record_trace,
+            split_order,
+            game_over,
+            preset,
+            lss_file,
+            encounter_route,
+            custom_splits_file,
             _splits_heading1,
             _splits_heading2,
             _splits_heading3,
@@ -2271,8 +2070,18 @@ impl fmt::Debug for Settings {
         dbg.field("start", start)
             .field("split", split)
             .field("reset", reset)
+            .field("undo_splits", undo_splits)
             .field("remove_loads", remove_loads)
+            .field("use_igt", use_igt)
+            .field("pause_on_crash", pause_on_crash)
             .field("count_encounters", count_encounters)
+            .field("record_trace", record_trace)
+            .field("split_order", split_order)
+            .field("game_over", game_over)
+            .field("preset", preset)
+            .field("lss_file", &&*lss_file.path)
+            .field("encounter_route", &&*encounter_route.path)
+            .field("custom_splits_file", &&*custom_splits_file.path)
             .field("splits", &DebugAsHex(enabled));
 
         #[cfg(testing)]
@@ -0,0 +1,64 @@
+//! Accounting for the loads that are removed from game time.
+//!
+//! The number of loads and the time that was removed are published as
+//! `loads_removed` and `load_time_removed`, and written to the log when the
+//! run ends, to verify that load removal works as expected.
+
+use std::time::{Duration, Instant};
+
+use crate::{log, timer_control::TimerControl};
+
+pub struct Loads {
+    started: Option<Instant>,
+    count: u32,
+    removed: Duration,
+}
+
+impl Loads {
+    pub const fn new() -> Self {
+        Self {
+            started: None,
+            count: 0,
+            removed: Duration::ZERO,
+        }
+    }
+
+    /// A load started, game time has been paused.
+    pub fn start(&mut self) {
+        self.started = Some(Instant::now());
+    }
+
+    /// A load finished, game time has been resumed.
+    pub fn end(&mut self, control: &mut impl TimerControl) {
+        let Some(started) = self.started.take() else {
+            return;
+        };
+        self.count += 1;
+        self.removed += started.elapsed();
+        control.set_variable_int("loads_removed", self.count);
+        control.set_variable("load_time_removed", &format_duration(self.removed));
+    }
+
+    /// The number of removed loads, and how long they took in total.
+    pub fn removed(&self) -> (u32, Duration) {
+        return (self.count, self.removed);
+    }
+
+    /// Log the removed loads, when the run is over.
+    pub fn finish(&self) {
+        if self.count == 0 {
+            return;
+        }
+        log!(
+            "Removed {} loads, {} in total",
+            self.count,
+            format_duration(self.removed)
+        );
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let millis = duration.subsec_millis();
+    return format!("{}:{:02}.{:03}", seconds / 60, seconds % 60, millis);
+}
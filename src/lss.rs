@@ -0,0 +1,186 @@
+//! Matching the segments of a LiveSplit splits file (`.lss`) to the splits.
+//!
+//! Only the `<Name>` of every `<Segment>` is looked at. A segment name is
+//! compared word by word with the name of every split, its setting key and
+//! its setting description, the split with the most words in common wins.
+//! Subsplit decorations like `-Kimahri` or `{Besaid}Kimahri` are ignored.
+//!
+//! Selecting a file in the settings enables exactly the matched splits.
+//! Segments without a matching split are logged and listed in the
+//! `unmatched_segments` variable, they still have to be split manually.
+//! Those manual splits move LiveSplit to the next segment without the auto
+//! splitter knowing, so importing such a file also turns off the split order.
+
+use asr::settings::Value;
+use strum::IntoEnumIterator as _;
+
+use crate::{
+    SeenSplits, Settings, Splits, log, presets, split_order::SplitOrder,
+    timer_control::TimerControl,
+};
+
+/// Imports the splits file from the settings whenever another one is selected.
+pub struct Import {
+    path: String,
+}
+
+impl Import {
+    /// The file that is already selected when the settings are loaded
+    /// has been imported before, and the splits may have been changed since.
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            path: settings.lss_file.path.to_string(),
+        }
+    }
+
+    pub fn update(&mut self, settings: &mut Settings, control: &mut impl TimerControl) {
+        if *settings.lss_file.path == *self.path {
+            return;
+        }
+        self.path = settings.lss_file.path.to_string();
+        if self.path.is_empty() {
+            return;
+        }
+
+        let lss = match std::fs::read_to_string(&self.path) {
+            Ok(lss) => lss,
+            Err(e) => {
+                log!("Could not read splits file {}: {}", self.path, e);
+                return;
+            }
+        };
+
+        let mut enabled = SeenSplits::empty();
+        let mut unmatched = String::new();
+        for segment in segment_names(&lss) {
+            match find_split(segment) {
+                Some(split) => {
+                    log!("Segment {} is split by {:?}", segment, split);
+                    enabled.insert(&split);
+                }
+                None => {
+                    log!("Segment {} has no matching split", segment);
+                    if unmatched.is_empty() == false {
+                        unmatched.push_str(", ");
+                    }
+                    unmatched.push_str(segment);
+                }
+            }
+        }
+
+        presets::apply(settings, |split| Some(enabled.contains(&split)));
+        if unmatched.is_empty() {
+            unmatched.push_str("none");
+        } else if settings.split_order != SplitOrder::Any {
+            log!("The split order can not be checked with unmatched segments, turning it off");
+            presets::store(|map| map.insert("split_order", &Value::from("Any")));
+            settings.update();
+        }
+        control.set_variable("unmatched_segments", &unmatched);
+
+        log!("Imported splits file {}: {:?}", self.path, settings);
+    }
+}
+
+/// The names of all segments in the splits file.
+pub fn segment_names(lss: &str) -> impl Iterator<Item = &str> {
+    let mut rest = lss;
+    return core::iter::from_fn(move || {
+        let segment = rest.find("<Segment>")?;
+        rest = &rest[segment..];
+        let start = rest.find("<Name>")? + "<Name>".len();
+        let len = rest[start..].find("</Name>")?;
+        let name = &rest[start..start + len];
+        rest = &rest[start + len..];
+        return Some(segment_name(name));
+    });
+}
+
+/// Removes subsplit decorations and CDATA around a segment name.
+fn segment_name(name: &str) -> &str {
+    let name = name.trim();
+    let name = name
+        .strip_prefix("<![CDATA[")
+        .and_then(|name| name.strip_suffix("]]>"))
+        .unwrap_or(name);
+    let name = name.strip_prefix('-').unwrap_or(name);
+    let name = match name.strip_prefix('{') {
+        Some(section) => section.split_once('}').map_or(name, |(_, name)| name),
+        None => name,
+    };
+    return name.trim();
+}
+
+/// The split that matches the segment name best, if any matches well enough.
+pub fn find_split(segment: &str) -> Option<Splits> {
+    let mut best = None;
+    let mut best_score = 0;
+    for split in Splits::iter() {
+        let Some(key) = split.setting_key() else {
+            continue;
+        };
+        let score = [
+            score(words(segment), camel_case_words(split.name())),
+            score(words(segment), words(key)),
+            score(words(segment), words(split.description())),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+        // Prefer the split of the default route when two match equally well.
+        let better = score > best_score
+            || (score == best_score
+                && score > 0
+                && split.enabled_by_default()
+                && best.is_some_and(|best: Splits| best.enabled_by_default() == false));
+        if better {
+            best = Some(split);
+            best_score = score;
+        }
+    }
+    return best;
+}
+
+/// How similar two sets of words are, from 0 to 100.
+/// Anything below 50 does not count as a match.
+fn score<'a, 'b>(
+    a: impl Iterator<Item = &'a str> + Clone,
+    b: impl Iterator<Item = &'b str> + Clone,
+) -> u32 {
+    let common = a
+        .clone()
+        .filter(|a| b.clone().any(|b| a.eq_ignore_ascii_case(b)))
+        .count();
+    let len = a.count().max(b.count());
+    if common == 0 || common * 2 < len {
+        return 0;
+    }
+    return u32::try_from(common * 100 / len).unwrap_or(0);
+}
+
+/// The words of a name, ignoring punctuation, XML entities and parentheses.
+fn words(name: &str) -> impl Iterator<Item = &str> + Clone {
+    let name = name.split_once('(').map_or(name, |(name, _)| name);
+    return name
+        .split(|c: char| c.is_ascii_alphanumeric() == false)
+        .filter(|word| word.is_empty() == false)
+        .filter(|word| matches!(*word, "amp" | "apos" | "quot" | "lt" | "gt") == false);
+}
+
+/// The words of a `CamelCase` name.
+fn camel_case_words(name: &str) -> impl Iterator<Item = &str> + Clone {
+    let mut rest = name;
+    return core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| c.is_ascii_uppercase())
+            .map_or(rest.len(), |(i, _)| i);
+        let (word, tail) = rest.split_at(end);
+        rest = tail;
+        return Some(word);
+    });
+}
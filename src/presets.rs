@@ -0,0 +1,103 @@
+//! Category presets that enable a curated set of splits with one choice.
+//!
+//! Choosing a preset writes the enabled state of every split to the settings,
+//! the same as clicking through all checkboxes. Any checkbox can still be
+//! changed afterwards, the preset is only applied again when it changes.
+//!
+//! Only the categories whose splits are all in the split table have a preset.
+//! Categories like Nemesis or No Sphere Grid split on parts of the game that
+//! the split table doesn't cover, import their splits file instead.
+
+use asr::settings::{Gui, Map, Value};
+use strum::IntoEnumIterator as _;
+
+use crate::{Settings, Splits, log, split_table::Trigger};
+
+#[derive(Gui, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Preset {
+    /// Choose the splits below
+    #[default]
+    Manual,
+    /// Any%
+    AnyPercent,
+    /// Any% with MRR Skip
+    AnyPercentMrrSkip,
+    /// Every split
+    AllSplits,
+    /// Only boss fights
+    Bosses,
+}
+
+impl Preset {
+    /// Whether the preset enables the split, `None` if it leaves it alone.
+    pub fn enables(self, split: Splits) -> Option<bool> {
+        return match self {
+            Self::Manual => None,
+            Self::AnyPercent => Some(split.enabled_by_default()),
+            Self::AnyPercentMrrSkip => Some(match split {
+                Splits::Mrr | Splits::Gui => false,
+                Splits::MrrSkip => true,
+                _ => split.enabled_by_default(),
+            }),
+            Self::AllSplits => Some(true),
+            Self::Bosses => {
+                Some(split.triggers().iter().any(|trigger| {
+                    matches!(trigger, Trigger::Battle { .. } | Trigger::HpZero { .. })
+                }))
+            }
+        };
+    }
+}
+
+/// Applies the preset from the settings whenever it is changed.
+pub struct Presets {
+    applied: Preset,
+}
+
+impl Presets {
+    /// The preset that is already selected when the settings are loaded
+    /// has been applied before, and may have been overridden since.
+    pub const fn new(settings: &Settings) -> Self {
+        Self {
+            applied: settings.preset,
+        }
+    }
+
+    pub fn update(&mut self, settings: &mut Settings) {
+        let preset = settings.preset;
+        if preset == self.applied {
+            return;
+        }
+        self.applied = preset;
+
+        apply(settings, |split| preset.enables(split));
+
+        log!("Applied preset {:?}: {:?}", preset, settings);
+    }
+}
+
+/// Writes the enabled state of the splits to the settings,
+/// leaving alone the splits for which `enables` returns `None`.
+pub fn apply(settings: &mut Settings, enables: impl Fn(Splits) -> Option<bool>) {
+    store(|map| {
+        for split in Splits::iter() {
+            if let (Some(enabled), Some(key)) = (enables(split), split.setting_key()) {
+                map.insert(key, &Value::from(enabled));
+            }
+        }
+    });
+    settings.update();
+}
+
+/// Changes the stored settings, without overwriting a change that the user
+/// made in the meantime: if the settings changed, the change is made again.
+pub fn store(change: impl Fn(&Map)) {
+    loop {
+        let old = Map::load();
+        let map = old.clone();
+        change(&map);
+        if map.store_if_unchanged(&old) {
+            return;
+        }
+    }
+}
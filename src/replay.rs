@@ -0,0 +1,516 @@
+//! Host-side replay of a recorded [trace](crate::trace).
+//!
+//! The recorded game values are fed tick by tick into the same
+//! `Timer` state machine that runs inside LiveSplit, with a simulated
+//! timer in place of the real one. The result is the sequence of timer
+//! events the splitter would have caused for that run.
+
+use asr::{time::Duration, timer::TimerState};
+use core::{fmt, str::FromStr};
+use strum::IntoEnumIterator as _;
+
+use crate::{
+    Action, BattleState, Formation, Level, Progress, Settings, Splits, Timer,
+    game_memory::{FakeMemory, GameMemory as _, Pointer},
+    timer_control::TimerControl,
+    trace,
+};
+
+/// A parsed trace, possibly spanning multiple attachments to the game.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    pub sections: Vec<Section>,
+}
+
+/// Everything that was recorded while attached to the game once.
+#[derive(Debug, Clone, Default)]
+pub struct Section {
+    pub frames: Vec<Frame>,
+}
+
+/// A single `TRACE` line.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub tick: u32,
+    pub values: Vec<(Pointer, Option<u64>)>,
+    pub timer: Option<TimerState>,
+    pub action: Option<RecordedAction>,
+}
+
+/// The action that the splitter took when the trace was recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordedAction {
+    Start,
+    StartPaused,
+    Reset,
+    Restart,
+    Split(Splits),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for Trace {
+    type Err = ParseError;
+
+    /// Parse all trace lines from a log, ignoring every other line.
+    fn from_str(log: &str) -> Result<Self, Self::Err> {
+        const PREFIX: &str = "[FFX]: ";
+
+        let mut trace = Trace::default();
+
+        for (number, line) in log.lines().enumerate() {
+            let error = |message: String| ParseError {
+                line: number + 1,
+                message,
+            };
+
+            let Some(start) = line.find("[FFX]: TRACE") else {
+                continue;
+            };
+            let line = line[start + PREFIX.len()..].trim_end();
+
+            if let Some(version) = line.strip_prefix("TRACE-BEGIN ") {
+                let version = version
+                    .parse::<u32>()
+                    .map_err(|_| error(format!("invalid version `{version}`")))?;
+                if version != trace::VERSION {
+                    return Err(error(format!("unsupported trace version {version}")));
+                }
+                trace.sections.push(Section::default());
+            } else if let Some(frame) = line.strip_prefix("TRACE ") {
+                let frame = parse_frame(frame).map_err(error)?;
+                let Some(section) = trace.sections.last_mut() else {
+                    return Err(error(String::from("TRACE line before TRACE-BEGIN")));
+                };
+                section.frames.push(frame);
+            }
+        }
+
+        return Ok(trace);
+    }
+}
+
+fn parse_frame(line: &str) -> Result<Frame, String> {
+    let mut parts = line.split_ascii_whitespace();
+    let tick = parts.next().unwrap_or_default();
+    let tick = tick
+        .parse::<u32>()
+        .map_err(|_| format!("invalid tick `{tick}`"))?;
+
+    let mut frame = Frame {
+        tick,
+        values: Vec::new(),
+        timer: None,
+        action: None,
+    };
+
+    for part in parts {
+        let Some((key, value)) = part.split_once('=') else {
+            return Err(format!("invalid entry `{part}`"));
+        };
+        match key {
+            "timer" => frame.timer = parse_timer(value),
+            "action" => frame.action = Some(parse_action(value)?),
+            index => {
+                let index = index
+                    .parse::<u8>()
+                    .map_err(|_| format!("invalid pointer `{index}`"))?;
+                // pointers that only exist in testing builds are skipped
+                let Ok(pointer) = Pointer::try_from(index) else {
+                    continue;
+                };
+                let value = match value {
+                    "-" => None,
+                    hex => Some(
+                        u64::from_str_radix(hex, 16)
+                            .map_err(|_| format!("invalid value `{hex}`"))?,
+                    ),
+                };
+                frame.values.push((pointer, value));
+            }
+        }
+    }
+
+    return Ok(frame);
+}
+
+fn parse_timer(code: &str) -> Option<TimerState> {
+    return match code {
+        "N" => Some(TimerState::NotRunning),
+        "R" => Some(TimerState::Running),
+        "P" => Some(TimerState::Paused),
+        "E" => Some(TimerState::Ended),
+        _ => None,
+    };
+}
+
+fn parse_action(action: &str) -> Result<RecordedAction, String> {
+    return match action {
+        "start" => Ok(RecordedAction::Start),
+        "start-paused" => Ok(RecordedAction::StartPaused),
+        "reset" => Ok(RecordedAction::Reset),
+        "restart" => Ok(RecordedAction::Restart),
+        _ => {
+            let Some(name) = action.strip_prefix("split:") else {
+                return Err(format!("invalid action `{action}`"));
+            };
+            split_by_name(name)
+                .map(RecordedAction::Split)
+                .ok_or_else(|| format!("unknown split `{name}`"))
+        }
+    };
+}
+
+/// Find a split by its name as it appears in the log.
+pub fn split_by_name(name: &str) -> Option<Splits> {
+    return Splits::iter().find(|split| format!("{split:?}") == name);
+}
+
+pub fn all_splits() -> impl Iterator<Item = Splits> {
+    return Splits::iter();
+}
+
+/// A timer event caused by the splitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    /// Tick since the start of the trace, counting across all sections.
+    pub tick: u64,
+    pub kind: EventKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Start,
+    Split(Splits),
+    SkipSplit,
+    UndoSplit,
+    Reset,
+    PauseGameTime,
+    ResumeGameTime,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>8} {}", self.tick, self.kind)
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Start => f.write_str("start"),
+            Self::Split(split) => write!(f, "split {split:?}"),
+            Self::SkipSplit => f.write_str("skip split"),
+            Self::UndoSplit => f.write_str("undo split"),
+            Self::Reset => f.write_str("reset"),
+            Self::PauseGameTime => f.write_str("pause game time"),
+            Self::ResumeGameTime => f.write_str("resume game time"),
+        }
+    }
+}
+
+/// Run the splitter over `trace` and return every timer event it caused.
+///
+/// The simulated timer starts in the first timer state that was recorded
+/// and afterwards only changes through the splitter's own actions.
+pub fn replay(settings: &Settings, trace: &Trace) -> Vec<Event> {
+    let state = trace
+        .sections
+        .iter()
+        .flat_map(|section| section.frames.first())
+        .find_map(|frame| frame.timer)
+        .unwrap_or(TimerState::NotRunning);
+
+    let mut control = SimulatedTimer {
+        state,
+        split_index: None,
+        tick: 0,
+        events: Vec::new(),
+    };
+    let mut timer = Timer::new();
+
+    for section in &trace.sections {
+        let mut memory = FakeMemory::new();
+        let mut frames = section.frames.iter().peekable();
+        let ticks = section.frames.last().map_or(0, |frame| frame.tick + 1);
+
+        for tick in 0..ticks {
+            while let Some(frame) = frames.next_if(|frame| frame.tick <= tick) {
+                for &(pointer, value) in &frame.values {
+                    match value {
+                        Some(value) => memory.set(pointer, value),
+                        None => memory.unset(pointer),
+                    };
+                }
+            }
+
+            let action = timer.update(settings, control.state, &memory, &mut control);
+            match action {
+                Action::Nothing => {}
+                Action::Start | Action::StartPaused => control.push(EventKind::Start),
+                Action::Reset => control.push(EventKind::Reset),
+                Action::Restart => {
+                    control.push(EventKind::Reset);
+                    control.push(EventKind::Start);
+                }
+                Action::Split(split) => control.push(EventKind::Split(split)),
+            }
+            action.send(&mut control);
+
+            control.tick += 1;
+        }
+
+        timer.reattach(settings, &mut control);
+    }
+
+    return control.events;
+}
+
+struct SimulatedTimer {
+    state: TimerState,
+    /// Only known for runs that were started during the replay.
+    split_index: Option<u64>,
+    tick: u64,
+    events: Vec<Event>,
+}
+
+impl SimulatedTimer {
+    fn push(&mut self, kind: EventKind) {
+        self.events.push(Event {
+            tick: self.tick,
+            kind,
+        });
+    }
+}
+
+impl TimerControl for SimulatedTimer {
+    fn state(&self) -> TimerState {
+        return self.state;
+    }
+
+    fn current_split_index(&self) -> Option<u64> {
+        return self.split_index;
+    }
+
+    fn start(&mut self) {
+        if matches!(self.state, TimerState::NotRunning) {
+            self.state = TimerState::Running;
+            self.split_index = Some(0);
+        }
+    }
+
+    fn split(&mut self) {
+        // the number of segments is unknown, so the simulated run never ends
+        self.split_index = self.split_index.map(|index| index + 1);
+    }
+
+    fn skip_split(&mut self) {
+        self.split_index = self.split_index.map(|index| index + 1);
+        self.push(EventKind::SkipSplit);
+    }
+
+    fn undo_split(&mut self) {
+        self.split_index = self.split_index.map(|index| index.saturating_sub(1));
+        self.push(EventKind::UndoSplit);
+    }
+
+    fn reset(&mut self) {
+        self.state = TimerState::NotRunning;
+        self.split_index = None;
+    }
+
+    fn pause_game_time(&mut self) {
+        self.push(EventKind::PauseGameTime);
+    }
+
+    fn resume_game_time(&mut self) {
+        self.push(EventKind::ResumeGameTime);
+    }
+
+    fn set_game_time(&mut self, _time: Duration) {
+        // would be an event for every second of the run
+    }
+
+    fn real_time(&self) -> Option<Duration> {
+        return None;
+    }
+
+    fn game_time(&self) -> Option<Duration> {
+        return None;
+    }
+
+    fn set_variable_int(&mut self, _key: &str, _value: u32) {}
+
+    fn set_variable(&mut self, _key: &str, _value: &str) {}
+}
+
+/// Something that happened in the game, as far as the splitter can tell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Level {
+        from: u32,
+        to: u32,
+    },
+    Story {
+        from: u32,
+        to: u32,
+    },
+    BattleStart {
+        map: u16,
+        formation: u16,
+    },
+    BattleEnd {
+        map: u16,
+        formation: u16,
+        escaped: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineEntry {
+    /// Tick since the start of the trace, counting across all sections.
+    pub tick: u64,
+    pub change: Change,
+}
+
+impl fmt::Display for TimelineEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>8} {}", self.tick, self.change)
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Level { from, to } => {
+                write!(f, "level  {} -> {}", LevelName(from), LevelName(to))
+            }
+            Self::Story { from, to } => {
+                write!(f, "story  {} -> {}", StoryName(from), StoryName(to))
+            }
+            Self::BattleStart { map, formation } => {
+                write!(f, "battle start {}", Encounter(map, formation))
+            }
+            Self::BattleEnd {
+                map,
+                formation,
+                escaped,
+            } => {
+                let how = if escaped { "escaped" } else { "over" };
+                write!(f, "battle {how} {}", Encounter(map, formation))
+            }
+        }
+    }
+}
+
+struct LevelName(u32);
+
+impl fmt::Display for LevelName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Level(self.0).name() {
+            Some(name) => write!(f, "{} ({name})", self.0),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+struct StoryName(u32);
+
+impl fmt::Display for StoryName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Progress(self.0).name() {
+            Some(name) => write!(f, "{} ({name})", self.0),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+struct Encounter(u16, u16);
+
+impl fmt::Display for Encounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [id1, id2] = self.1.to_ne_bytes();
+        write!(f, "map {} formation {id1}/{id2}", self.0)
+    }
+}
+
+/// Collect level transitions, story progression changes and battles from `trace`.
+pub fn timeline(trace: &Trace) -> Vec<TimelineEntry> {
+    let mut entries = Vec::new();
+    let mut offset = 0_u64;
+
+    for section in &trace.sections {
+        let mut memory = FakeMemory::new();
+        let mut level = None::<Level>;
+        let mut story = None::<Progress>;
+        let mut battle = BattleState::default();
+
+        for frame in &section.frames {
+            for &(pointer, value) in &frame.values {
+                match value {
+                    Some(value) => memory.set(pointer, value),
+                    None => memory.unset(pointer),
+                };
+            }
+
+            let tick = offset + u64::from(frame.tick);
+            let mut push = |change| entries.push(TimelineEntry { tick, change });
+
+            if let Some(current) = memory.read::<Level>(Pointer::Level) {
+                if let Some(old) = level.replace(current).filter(|old| *old != current) {
+                    push(Change::Level {
+                        from: old.0,
+                        to: current.0,
+                    });
+                }
+            }
+
+            if let Some(current) = memory.read::<Progress>(Pointer::StoryProgression) {
+                if let Some(old) = story.replace(current).filter(|old| *old != current) {
+                    push(Change::Story {
+                        from: old.0,
+                        to: current.0,
+                    });
+                }
+            }
+
+            let old = battle;
+            battle = memory.read(Pointer::BattleState).unwrap_or_default();
+            let map = memory.read::<u16>(Pointer::MapId).unwrap_or_default();
+            let formation = memory
+                .read::<Formation>(Pointer::FormationId)
+                .unwrap_or_default()
+                .0;
+
+            if battle.in_battle() && old.in_battle() == false {
+                push(Change::BattleStart { map, formation });
+            }
+            if battle.is_over() && old.is_over() == false {
+                push(Change::BattleEnd {
+                    map,
+                    formation,
+                    escaped: battle.escaped(),
+                });
+            }
+        }
+
+        offset += section
+            .frames
+            .last()
+            .map_or(0, |frame| u64::from(frame.tick) + 1);
+    }
+
+    return entries;
+}
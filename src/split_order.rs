@@ -0,0 +1,89 @@
+//! Keeping the splits in sync with the segments in LiveSplit.
+//!
+//! The enabled splits, in the order of the split table, are expected to be
+//! the segments of the splits file. When a split happens, its position in that
+//! list is compared with the segment that LiveSplit is currently on.
+//! Custom splits can be set up for any point of the route, so they have no
+//! position. They still move LiveSplit to the next segment, which shifts the
+//! position of every split after them, so nothing is checked while any custom
+//! split is enabled.
+
+use asr::settings::Gui;
+use strum::IntoEnumIterator as _;
+
+use crate::{Settings, Splits, log, timer_control::TimerControl};
+
+#[derive(Gui, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SplitOrder {
+    /// Split for every enabled split, in any order
+    #[default]
+    Any,
+    /// Skip the segments of missed splits
+    SkipMissed,
+    /// Only split for the current segment
+    Strict,
+}
+
+impl SplitOrder {
+    /// Whether the split may happen now, skipping any segments before it.
+    pub fn allows(
+        self,
+        settings: &Settings,
+        split: Splits,
+        control: &mut impl TimerControl,
+    ) -> bool {
+        if self == Self::Any {
+            return true;
+        }
+        let (Some(position), Some(current)) =
+            (position(settings, split), control.current_split_index())
+        else {
+            return true;
+        };
+
+        if position == current {
+            return true;
+        }
+        if position < current {
+            log!(
+                "Ignoring split {:?} for segment {}, the timer is already at segment {}",
+                split,
+                position,
+                current
+            );
+            return false;
+        }
+        if self == Self::Strict {
+            log!(
+                "Ignoring split {:?} for segment {}, the timer is still at segment {}",
+                split,
+                position,
+                current
+            );
+            return false;
+        }
+
+        log!(
+            "Skipping {} missed segments before {:?}",
+            position - current,
+            split
+        );
+        for _ in current..position {
+            control.skip_split();
+        }
+        return true;
+    }
+}
+
+/// The index of the split's segment, when only the enabled splits are segments.
+fn position(settings: &Settings, split: Splits) -> Option<u64> {
+    split.setting_key()?;
+    if Splits::CUSTOM.iter().any(|custom| settings.filter(*custom)) {
+        return None;
+    }
+    let before = Splits::iter()
+        .take_while(|s| *s != split)
+        .filter(|s| s.setting_key().is_some() && settings.filter(*s))
+        .count();
+    return u64::try_from(before).ok();
+}
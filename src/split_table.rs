@@ -0,0 +1,590 @@
+//! The machinery behind the split table in `lib.rs`.
+//!
+//! Every split is a single row in the `splits!` invocation, which declares
+//! its setting and the [`Trigger`]s that cause it. From that table, the macro
+//! generates the `Splits` enum, the `Settings` struct and `Settings::filter`,
+//! and the detection in `Level::split` and `Progress::split_*` evaluates
+//! the triggers of every split.
+
+use core::ops::ControlFlow;
+
+use crate::{
+    Formation, GameMemory, NO_SPLIT, Read, Settings, Splits, Splitter, custom_splits::Rules,
+};
+use strum::IntoEnumIterator as _;
+
+/// What needs to happen in the game for a split to trigger.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Trigger {
+    /// Moving from one level to another, where `None` matches any level,
+    /// optionally only at a specific story progression.
+    Level {
+        from: Option<u32>,
+        to: Option<u32>,
+        story: Option<u32>,
+    },
+    /// A battle ending while the story is at a specific progression,
+    /// optionally only for a specific encounter.
+    Battle {
+        story: u32,
+        encounter: Option<Encounter>,
+        fanfare: bool,
+        escape: bool,
+    },
+    /// The story progressing past a value,
+    /// optionally only to a specific value or during a specific cutscene.
+    Advance {
+        from: u32,
+        to: Option<u32>,
+        cutscene: Option<u32>,
+    },
+    /// The cutscene changing while the story is at a specific progression,
+    /// optionally only during a specific encounter.
+    Cutscene {
+        story: u32,
+        from: u32,
+        to: u32,
+        encounter: Option<Encounter>,
+    },
+    /// The HP of the first enemy being zero when the final battle flag is set.
+    HpZero { story: u32 },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Encounter {
+    pub map_id: u16,
+    pub formation: Formation,
+}
+
+impl Encounter {
+    pub fn matches(self, read: &mut Read<'_, impl GameMemory>) -> bool {
+        return read.map_id().current == self.map_id
+            && read.formation_id().current == self.formation;
+    }
+}
+
+pub const fn level(from: u32, to: u32) -> Trigger {
+    Trigger::Level {
+        from: Some(from),
+        to: Some(to),
+        story: None,
+    }
+}
+
+pub const fn battle(story: u32) -> Trigger {
+    Trigger::Battle {
+        story,
+        encounter: None,
+        fanfare: false,
+        escape: false,
+    }
+}
+
+pub const fn advance(from: u32) -> Trigger {
+    Trigger::Advance {
+        from,
+        to: None,
+        cutscene: None,
+    }
+}
+
+pub const fn cutscene(story: u32, from: u32, to: u32) -> Trigger {
+    Trigger::Cutscene {
+        story,
+        from,
+        to,
+        encounter: None,
+    }
+}
+
+pub const fn hp_zero(story: u32) -> Trigger {
+    Trigger::HpZero { story }
+}
+
+impl Trigger {
+    /// Only trigger during the encounter with the given map and formation.
+    pub const fn at(self, map_id: u16, id1: u8, id2: u8) -> Self {
+        let at = Some(Encounter {
+            map_id,
+            formation: Formation::new(id1, id2),
+        });
+        match self {
+            Self::Battle {
+                story,
+                fanfare,
+                escape,
+                ..
+            } => Self::Battle {
+                story,
+                encounter: at,
+                fanfare,
+                escape,
+            },
+            Self::Cutscene {
+                story, from, to, ..
+            } => Self::Cutscene {
+                story,
+                from,
+                to,
+                encounter: at,
+            },
+            _ => panic!("only battles and cutscenes happen at an encounter"),
+        }
+    }
+
+    /// Only trigger a level transition at the given story progression.
+    pub const fn story(self, story: u32) -> Self {
+        match self {
+            Self::Level { from, to, .. } => Self::Level {
+                from,
+                to,
+                story: Some(story),
+            },
+            _ => panic!("only level transitions can require a story progression"),
+        }
+    }
+
+    /// Only trigger a story advance that goes to the given progression.
+    pub const fn to(self, to: u32) -> Self {
+        match self {
+            Self::Advance { from, cutscene, .. } => Self::Advance {
+                from,
+                to: Some(to),
+                cutscene,
+            },
+            _ => panic!("only story advances can go to a story progression"),
+        }
+    }
+
+    /// Only trigger a story advance during the given cutscene.
+    pub const fn during(self, cutscene: u32) -> Self {
+        match self {
+            Self::Advance { from, to, .. } => Self::Advance {
+                from,
+                to,
+                cutscene: Some(cutscene),
+            },
+            _ => panic!("only story advances can happen during a cutscene"),
+        }
+    }
+
+    /// Only trigger a battle that ended with the victory fanfare.
+    pub const fn fanfare(self) -> Self {
+        match self {
+            Self::Battle {
+                story,
+                encounter,
+                escape,
+                ..
+            } => Self::Battle {
+                story,
+                encounter,
+                fanfare: true,
+                escape,
+            },
+            _ => panic!("only battles have a fanfare"),
+        }
+    }
+
+    /// Also trigger a battle that was escaped from.
+    pub const fn escapable(self) -> Self {
+        match self {
+            Self::Battle {
+                story,
+                encounter,
+                fanfare,
+                ..
+            } => Self::Battle {
+                story,
+                encounter,
+                fanfare,
+                escape: true,
+            },
+            _ => panic!("only battles can be escaped"),
+        }
+    }
+}
+
+impl Trigger {
+    /// Whether the trigger can only have happened before the story progression.
+    /// Level transitions that don't require a story progression are never behind.
+    const fn is_behind(self, progress: u32) -> bool {
+        return match self {
+            Self::Level { story, .. } => match story {
+                Some(story) => story < progress,
+                None => false,
+            },
+            Self::Battle { story, .. } | Self::HpZero { story } => story < progress,
+            Self::Advance { from, to, .. } => match to {
+                Some(to) => to <= progress,
+                None => from < progress,
+            },
+            Self::Cutscene { story, .. } => story < progress,
+        };
+    }
+
+    /// Whether the story progression is the same right before the trigger,
+    /// so that a save from right before it has the story progression of the split.
+    /// Level transitions don't count, a save right after them has it as well.
+    pub const fn keeps_story(self) -> bool {
+        return matches!(
+            self,
+            Self::Battle { .. } | Self::Cutscene { .. } | Self::HpZero { .. }
+        );
+    }
+}
+
+/// The splits that are already behind the story progression.
+pub fn splits_behind(progress: u32) -> impl Iterator<Item = Splits> {
+    return Splits::iter().filter(move |split| {
+        let triggers = split.triggers();
+        triggers.is_empty() == false && triggers.iter().all(|trigger| trigger.is_behind(progress))
+    });
+}
+
+/// Whether the current battle is one that a split is waiting for.
+pub fn is_boss_battle(read: &mut Read<'_, impl GameMemory>) -> bool {
+    return boss_battle(read).is_some();
+}
+
+/// The split that is waiting for the current battle, if any.
+pub fn boss_battle(read: &mut Read<'_, impl GameMemory>) -> Option<Splits> {
+    let progress = read.story_progression().current.0;
+    return Splits::iter().find(|split| {
+        split.triggers().iter().any(|trigger| match *trigger {
+            Trigger::Battle {
+                story, encounter, ..
+            } => story == progress && encounter.is_none_or(|encounter| encounter.matches(read)),
+            Trigger::HpZero { story } => story == progress,
+            _ => false,
+        })
+    });
+}
+
+/// Find the first enabled split that has a trigger for which `matches` is true.
+/// The custom splits from the settings are checked after the built-in ones.
+pub fn find_split(
+    settings: &Settings,
+    custom: &Rules,
+    mut matches: impl FnMut(Trigger) -> bool,
+) -> Splitter {
+    for split in Splits::iter().filter(|split| settings.filter(*split)) {
+        if split.triggers().iter().any(|trigger| matches(*trigger)) {
+            return ControlFlow::Break(split);
+        }
+    }
+    for (split, trigger) in settings.custom_triggers(custom) {
+        if matches(trigger) {
+            return ControlFlow::Break(split);
+        }
+    }
+    return NO_SPLIT;
+}
+
+/// Declares all splits, see the invocation in `lib.rs`.
+macro_rules! splits {
+    (
+        options {
+            $(
+                $(#[doc = $option_doc:tt])*
+                $option:ident = $option_default:tt,
+            )*
+        }
+        splits {
+            $(
+                $(#[doc = $doc:tt])*
+                $split:ident($setting:ident = $default:tt) => [$($trigger:expr),* $(,)?],
+            )*
+        }
+        custom_splits {
+            $(
+                $custom_split:ident {
+                    $(#[doc = $kind_doc:tt])*
+                    kind: $kind:ident,
+                    $(#[doc = $from_doc:tt])*
+                    from: $from:ident,
+                    $(#[doc = $to_doc:tt])*
+                    to: $to:ident,
+                    $(#[doc = $story_doc:tt])*
+                    story: $story:ident,
+                },
+            )*
+        }
+        testing_options {
+            $(
+                $(#[doc = $testing_option_doc:tt])*
+                $testing_option:ident = $testing_option_default:tt,
+            )*
+        }
+        testing_splits {
+            $(
+                $(#[doc = $testing_doc:tt])*
+                $testing_split:ident($testing_setting:ident = $testing_default:tt)
+                    => [$($testing_trigger:expr),* $(,)?],
+            )*
+        }
+    ) => {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, EnumIter)]
+        #[repr(u8)]
+        pub enum Splits {
+            $($split,)*
+            $($custom_split,)*
+            $(
+                #[cfg(testing)]
+                $testing_split,
+            )*
+        }
+
+        #[derive(Gui)]
+        pub struct Settings {
+            $(
+                $(#[doc = $option_doc])*
+                #[default = $option_default]
+                $option: bool,
+            )*
+
+            /// Split order: Compare the splits with the current segment in LiveSplit
+            split_order: SplitOrder,
+
+            /// Game over: What to do when the party is defeated
+            game_over: GameOverAction,
+
+            /// Category preset, the splits can still be changed below
+            preset: Preset,
+
+            /// Splits file: Enable exactly the splits of a LiveSplit .lss file
+            #[filter(("LiveSplit Splits", "*.lss"))]
+            lss_file: FileSelect,
+
+            /// Encounter route: Expected encounters per split, e.g. `Kimahri = 3`
+            #[filter(("Encounter Route", "*.txt"))]
+            encounter_route: FileSelect,
+
+            /// Splits: Enable the settings that match your splits!
+            _splits_heading1: Heading,
+
+            /// You don't need to enable all, only what you want to split.
+            _splits_heading2: Heading,
+
+            /// Anything else not mentioned here can still be split manually.
+            _splits_heading3: Heading,
+
+            $(
+                $(#[doc = $doc])*
+                #[default = $default]
+                $setting: bool,
+            )*
+
+            /// Custom splits: Split on something that is not listed above
+            _custom_heading: Heading,
+
+            /// Custom splits file: Rules for custom splits, e.g. `Custom1 = battle 1540 map 65 formation 0/0`
+            #[filter(("Custom Splits", "*.txt"))]
+            custom_splits_file: FileSelect,
+
+            $(
+                $(#[doc = $kind_doc])*
+                $kind: CustomKind,
+
+                $(#[doc = $from_doc])*
+                $from: LevelChoice,
+
+                $(#[doc = $to_doc])*
+                $to: LevelChoice,
+
+                $(#[doc = $story_doc])*
+                $story: StoryChoice,
+            )*
+
+            #[cfg(testing)]
+            /// FOR TESTING: The rest of the settings is for testing things
+            _test_heading: Heading,
+
+            $(
+                #[cfg(testing)]
+                $(#[doc = $testing_option_doc])*
+                #[default = $testing_option_default]
+                $testing_option: bool,
+            )*
+
+            $(
+                #[cfg(testing)]
+                $(#[doc = $testing_doc])*
+                #[default = $testing_default]
+                $testing_setting: bool,
+            )*
+        }
+
+        impl Settings {
+            fn filter(&self, split_on: Splits) -> bool {
+                return match split_on {
+                    $(Splits::$split => self.$setting,)*
+                    $(Splits::$custom_split => self.$kind != CustomKind::Off,)*
+                    $(
+                        #[cfg(testing)]
+                        Splits::$testing_split => self.$testing_setting,
+                    )*
+                };
+            }
+
+            fn custom_triggers(
+                &self,
+                rules: &custom_splits::Rules,
+            ) -> impl Iterator<Item = (Splits, Trigger)> {
+                return [
+                    $((
+                        Splits::$custom_split,
+                        custom_splits::trigger(
+                            self.$kind,
+                            self.$from,
+                            self.$to,
+                            self.$story,
+                            rules.get(Splits::$custom_split),
+                        ),
+                    ),)*
+                ]
+                .into_iter()
+                .filter_map(|(split, trigger)| Some((split, trigger?)));
+            }
+        }
+
+        impl Splits {
+            /// The custom splits, in the order of their settings.
+            const CUSTOM: &[Self] = &[$(Self::$custom_split),*];
+
+            fn enabled_by_default(self) -> bool {
+                return match self {
+                    $(Self::$split => $default,)*
+                    $(Self::$custom_split => false,)*
+                    $(
+                        #[cfg(testing)]
+                        Self::$testing_split => $testing_default,
+                    )*
+                };
+            }
+
+            /// The key of the split's setting, for the splits that presets can change.
+            fn setting_key(self) -> Option<&'static str> {
+                return match self {
+                    $(Self::$split => Some(stringify!($setting)),)*
+                    $(Self::$custom_split => None,)*
+                    $(
+                        #[cfg(testing)]
+                        Self::$testing_split => None,
+                    )*
+                };
+            }
+
+            /// The name of the split, as written in the split table.
+            pub fn name(self) -> &'static str {
+                return match self {
+                    $(Self::$split => stringify!($split),)*
+                    $(Self::$custom_split => stringify!($custom_split),)*
+                    $(
+                        #[cfg(testing)]
+                        Self::$testing_split => stringify!($testing_split),
+                    )*
+                };
+            }
+
+            /// The description of the split's setting.
+            pub fn description(self) -> &'static str {
+                let description = match self {
+                    $(Self::$split => concat!($($doc),*),)*
+                    $(Self::$custom_split => "",)*
+                    $(
+                        #[cfg(testing)]
+                        Self::$testing_split => concat!($($testing_doc),*),
+                    )*
+                };
+                return description.trim();
+            }
+
+            fn triggers(self) -> &'static [Trigger] {
+                match self {
+                    $(
+                        Self::$split => {
+                            const TRIGGERS: &[Trigger] = &[$($trigger),*];
+                            TRIGGERS
+                        }
+                    )*
+                    $(Self::$custom_split => &[],)*
+                    $(
+                        #[cfg(testing)]
+                        Self::$testing_split => {
+                            const TRIGGERS: &[Trigger] = &[$($testing_trigger),*];
+                            TRIGGERS
+                        }
+                    )*
+                }
+            }
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        impl Settings {
+            /// The settings with every option at its default value,
+            /// for running the splitter outside of LiveSplit.
+            pub fn defaults() -> Self {
+                return Self {
+                    $($option: $option_default,)*
+                    split_order: SplitOrder::Any,
+                    game_over: GameOverAction::Nothing,
+                    preset: Preset::Manual,
+                    lss_file: FileSelect::default(),
+                    encounter_route: FileSelect::default(),
+                    _splits_heading1: Heading,
+                    _splits_heading2: Heading,
+                    _splits_heading3: Heading,
+                    $($setting: $default,)*
+                    _custom_heading: Heading,
+                    custom_splits_file: FileSelect::default(),
+                    $(
+                        $kind: CustomKind::Off,
+                        $from: LevelChoice::Any,
+                        $to: LevelChoice::Any,
+                        $story: StoryChoice::Unset,
+                    )*
+                    #[cfg(testing)]
+                    _test_heading: Heading,
+                    $(
+                        #[cfg(testing)]
+                        $testing_option: $testing_option_default,
+                    )*
+                    $(
+                        #[cfg(testing)]
+                        $testing_setting: $testing_default,
+                    )*
+                };
+            }
+
+            /// Enable or disable a single split.
+            /// Custom splits are enabled by their own settings instead.
+            pub fn set_split(&mut self, split: Splits, enabled: bool) {
+                let setting = match split {
+                    $(Splits::$split => &mut self.$setting,)*
+                    $(Splits::$custom_split => return,)*
+                    $(
+                        #[cfg(testing)]
+                        Splits::$testing_split => &mut self.$testing_setting,
+                    )*
+                };
+                *setting = enabled;
+            }
+
+            /// Set one of the general options by its settings key.
+            /// Returns `false` if there is no such option.
+            pub fn set_option(&mut self, key: &str, value: bool) -> bool {
+                let option = match key {
+                    $(stringify!($option) => &mut self.$option,)*
+                    $(
+                        #[cfg(testing)]
+                        stringify!($testing_option) => &mut self.$testing_option,
+                    )*
+                    _ => return false,
+                };
+                *option = value;
+                return true;
+            }
+        }
+    };
+}
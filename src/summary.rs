@@ -0,0 +1,101 @@
+//! A machine-readable summary of the run, written to the log at the end.
+//!
+//! When the final split happens, every split of the run is logged as one
+//! line of JSON, prefixed with `summary `. The real time and game time are
+//! the ones of the LiveSplit timer at the split, in seconds, or `null` when
+//! the timer could not tell them.
+
+use std::time::Duration as StdDuration;
+
+use asr::time::Duration;
+
+use crate::{GameMemory, Read, Splits, log, timer_control::TimerControl};
+
+struct SplitRecord {
+    split: Splits,
+    real_time: Option<Duration>,
+    game_time: Option<Duration>,
+    encounters: u32,
+    battles: usize,
+    loads_removed: u32,
+    load_time_removed: StdDuration,
+    story: u32,
+}
+
+pub struct Summary {
+    encounters: u32,
+    story: u32,
+    splits: Vec<SplitRecord>,
+}
+
+impl Summary {
+    pub const fn new() -> Self {
+        Self {
+            encounters: 0,
+            story: 0,
+            splits: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, read: &mut Read<'_, impl GameMemory>) {
+        self.encounters = read.encounter_count().current;
+        self.story = read.story_progression().current.0;
+    }
+
+    pub fn split(
+        &mut self,
+        split: Splits,
+        battles: usize,
+        loads_removed: (u32, StdDuration),
+        control: &impl TimerControl,
+    ) {
+        self.splits.push(SplitRecord {
+            split,
+            real_time: control.real_time(),
+            game_time: control.game_time(),
+            encounters: self.encounters,
+            battles,
+            loads_removed: loads_removed.0,
+            load_time_removed: loads_removed.1,
+            story: self.story,
+        });
+
+        if split == Splits::YuYevon {
+            self.write();
+        }
+    }
+
+    /// The split was undone, it is not part of the run anymore.
+    pub fn undo(&mut self, split: Splits) {
+        if self
+            .splits
+            .last()
+            .is_some_and(|record| record.split == split)
+        {
+            self.splits.pop();
+        }
+    }
+
+    fn write(&self) {
+        for record in &self.splits {
+            log!(
+                "summary {{\"split\":\"{:?}\",\"real_time\":{},\"game_time\":{},\"encounters\":{},\"battles\":{},\"loads_removed\":{},\"load_time_removed\":{:.3},\"story\":{}}}",
+                record.split,
+                seconds(record.real_time),
+                seconds(record.game_time),
+                record.encounters,
+                record.battles,
+                record.loads_removed,
+                record.load_time_removed.as_secs_f64(),
+                record.story
+            );
+        }
+    }
+}
+
+fn seconds(time: Option<Duration>) -> String {
+    return match time {
+        Some(time) => format!("{:.3}", time.as_seconds_f64()),
+        None => String::from("null"),
+    };
+}
@@ -0,0 +1,667 @@
+//! Tests for the split detection, driven by a [`FakeMemory`] on the host,
+//! and for replaying the traces in `fixtures/`.
+//!
+//! Run them with the host target, e.g.
+//! `cargo test --target x86_64-unknown-linux-gnu`.
+
+use std::collections::BTreeMap;
+
+use asr::{time::Duration, timer::TimerState};
+use strum::IntoEnumIterator as _;
+
+use crate::{
+    Action, BattleState, Formation, Level, Progress, Settings, Splits, Timer,
+    custom_splits::{self, CustomKind, LevelChoice, StoryChoice},
+    game_memory::{FakeMemory, Pointer},
+    lss,
+    presets::Preset,
+    replay::{self, Event, EventKind, RecordedAction, Trace},
+    split_order::SplitOrder,
+    split_table::{self, advance, battle, level},
+    timer_control::TimerControl,
+};
+
+/// A timer that only keeps track of its state, the current segment
+/// and the variables.
+struct FakeTimer {
+    state: TimerState,
+    split_index: Option<u64>,
+    variables: BTreeMap<String, u32>,
+    texts: BTreeMap<String, String>,
+}
+
+impl TimerControl for FakeTimer {
+    fn state(&self) -> TimerState {
+        return self.state;
+    }
+
+    fn current_split_index(&self) -> Option<u64> {
+        return self.split_index;
+    }
+
+    fn start(&mut self) {
+        self.state = TimerState::Running;
+        self.split_index = Some(0);
+    }
+
+    fn split(&mut self) {
+        self.split_index = self.split_index.map(|index| index + 1);
+    }
+
+    fn skip_split(&mut self) {
+        self.split_index = self.split_index.map(|index| index + 1);
+    }
+
+    fn undo_split(&mut self) {
+        self.split_index = self.split_index.map(|index| index.saturating_sub(1));
+    }
+
+    fn reset(&mut self) {
+        self.state = TimerState::NotRunning;
+        self.split_index = None;
+    }
+
+    fn pause_game_time(&mut self) {}
+
+    fn resume_game_time(&mut self) {}
+
+    fn set_game_time(&mut self, _time: Duration) {}
+
+    fn real_time(&self) -> Option<Duration> {
+        return None;
+    }
+
+    fn game_time(&self) -> Option<Duration> {
+        return None;
+    }
+
+    fn set_variable_int(&mut self, key: &str, value: u32) {
+        self.variables.insert(key.to_owned(), value);
+    }
+
+    fn set_variable(&mut self, key: &str, value: &str) {
+        self.texts.insert(key.to_owned(), value.to_owned());
+    }
+}
+
+/// The splitter during a run, with the game at a save that is loaded.
+struct Run {
+    settings: Settings,
+    timer: Timer,
+    game: FakeMemory,
+    control: FakeTimer,
+}
+
+impl Run {
+    fn new(level: u32, story: u32) -> Self {
+        return Self::with(Settings::defaults(), level, story);
+    }
+
+    fn with(settings: Settings, level: u32, story: u32) -> Self {
+        let mut game = FakeMemory::new();
+        for pointer in [
+            Pointer::Loading,
+            Pointer::EncounterCount,
+            Pointer::BattleState,
+            Pointer::CutsceneType,
+            Pointer::MapId,
+            Pointer::FormationId,
+            Pointer::YuYevon,
+            Pointer::HpEnemyA,
+            Pointer::SelectScreen,
+            Pointer::CursorPosition,
+            Pointer::Input,
+            Pointer::Igt,
+        ] {
+            game.set(pointer, 0);
+        }
+        game.set(Pointer::Level, u64::from(level))
+            .set(Pointer::StoryProgression, u64::from(story));
+
+        let mut run = Self {
+            settings,
+            timer: Timer::new(),
+            game,
+            control: FakeTimer {
+                state: TimerState::Running,
+                split_index: Some(0),
+                variables: BTreeMap::new(),
+                texts: BTreeMap::new(),
+            },
+        };
+        assert_eq!(run.tick(), None, "nothing happens when attaching");
+        return run;
+    }
+
+    fn set(&mut self, pointer: Pointer, value: u32) -> &mut Self {
+        self.game.set(pointer, u64::from(value));
+        return self;
+    }
+
+    fn encounter(&mut self, map_id: u16, id1: u8, id2: u8) -> &mut Self {
+        self.game
+            .set(Pointer::MapId, u64::from(map_id))
+            .set(Pointer::FormationId, u64::from(Formation::new(id1, id2).0));
+        return self;
+    }
+
+    /// Run a single tick and return the split, if there was one.
+    fn tick(&mut self) -> Option<Splits> {
+        let action = self.timer.update(
+            &self.settings,
+            self.control.state,
+            &self.game,
+            &mut self.control,
+        );
+        action.send(&mut self.control);
+        return match action {
+            Action::Split(split) => Some(split),
+            Action::Nothing => None,
+            otherwise => panic!("unexpected action {otherwise:?}"),
+        };
+    }
+
+    /// Fight a battle to the end and return the split, if there was one.
+    fn win_battle(&mut self) -> Option<Splits> {
+        self.set(Pointer::BattleState, BattleState::ONGOING);
+        assert_eq!(self.tick(), None, "no split while the battle is ongoing");
+        self.set(
+            Pointer::BattleState,
+            BattleState::IS_OVER | BattleState::FANFARE,
+        );
+        return self.tick();
+    }
+
+    /// Lose a battle and return to the title screen, then load a save.
+    fn game_over(&mut self, level: u32, story: u32) {
+        self.set(Pointer::BattleState, BattleState::ONGOING);
+        assert_eq!(self.tick(), None, "no split while the battle is ongoing");
+        self.set(Pointer::BattleState, BattleState::IS_OVER);
+        assert_eq!(self.tick(), None, "no split for a lost battle");
+        self.set(Pointer::Level, Level::NEW_GAME);
+        assert_eq!(self.tick(), None, "no split on the title screen");
+        self.set(Pointer::BattleState, 0)
+            .set(Pointer::Level, level)
+            .set(Pointer::StoryProgression, story);
+        assert_eq!(self.tick(), None, "no split when loading a save");
+    }
+}
+
+#[test]
+fn level_transition() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.set(Pointer::Level, Level::KILIKA_RESIDENTIAL_AREA);
+    assert_eq!(run.tick(), Some(Splits::Kilika));
+}
+
+#[test]
+fn level_transition_in_the_other_direction() {
+    let mut run = Run::new(Level::KILIKA_RESIDENTIAL_AREA, 330);
+    run.set(Pointer::Level, Level::KILIKA_WOODS);
+    assert_eq!(run.tick(), None);
+}
+
+#[test]
+fn level_transition_splits_once() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.set(Pointer::Level, Level::KILIKA_RESIDENTIAL_AREA);
+    assert_eq!(run.tick(), Some(Splits::Kilika));
+    run.set(Pointer::Level, Level::KILIKA_WOODS);
+    assert_eq!(run.tick(), None);
+    run.set(Pointer::Level, Level::KILIKA_RESIDENTIAL_AREA);
+    assert_eq!(run.tick(), None);
+}
+
+#[test]
+fn zone_names() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.set(Pointer::Level, Level::KILIKA_RESIDENTIAL_AREA);
+    assert_eq!(run.tick(), Some(Splits::Kilika));
+    assert_eq!(run.control.texts["zone"], "Kilika Residential Area");
+    run.set(Pointer::Level, 999);
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.texts["zone"], "Level 999");
+}
+
+#[test]
+fn disabled_split() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.settings.set_split(Splits::Kilika, false);
+    run.set(Pointer::Level, Level::KILIKA_RESIDENTIAL_AREA);
+    assert_eq!(run.tick(), None);
+}
+
+#[test]
+fn battle() {
+    let mut run = Run::new(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    assert_eq!(run.win_battle(), Some(Splits::Kimahri));
+}
+
+#[test]
+fn battle_at_encounter() {
+    let mut run = Run::new(Level::HIGHROAD_CENTRAL, Progress::GARUDA);
+    run.encounter(17, 0, 1);
+    assert_eq!(run.win_battle(), Some(Splits::Garuda));
+}
+
+#[test]
+fn battle_at_other_encounter() {
+    let mut run = Run::new(Level::HIGHROAD_CENTRAL, Progress::GARUDA);
+    run.encounter(17, 0, 0);
+    assert_eq!(run.win_battle(), None);
+    run.encounter(18, 0, 1);
+    assert_eq!(run.win_battle(), None);
+}
+
+#[test]
+fn escaped_battle() {
+    let mut run = Run::new(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    run.set(Pointer::BattleState, BattleState::ONGOING);
+    assert_eq!(run.tick(), None);
+    run.set(
+        Pointer::BattleState,
+        BattleState::IS_OVER | BattleState::ESCAPED,
+    );
+    assert_eq!(run.tick(), None);
+}
+
+#[test]
+fn enemy_hp() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.set(Pointer::HpEnemyA, 1000)
+        .set(Pointer::BattleState, BattleState::ONGOING);
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.variables["enemy_a_hp"], 1000);
+    // the other slots could not be read
+    assert_eq!(run.control.variables.get("enemy_b_hp"), None);
+    run.set(Pointer::HpEnemyA, 0).set(
+        Pointer::BattleState,
+        BattleState::IS_OVER | BattleState::FANFARE,
+    );
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.texts["enemy_a_hp"], "");
+    assert_eq!(run.control.texts["enemy_b_hp"], "");
+}
+
+#[test]
+fn undo_battle_split_after_game_over() {
+    let mut run = Run::new(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    run.settings.undo_splits = true;
+    assert_eq!(run.win_battle(), Some(Splits::Kimahri));
+    // the save right before the fight has the story progression of the split
+    run.game_over(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    assert_eq!(run.control.split_index, Some(0));
+    assert_eq!(run.win_battle(), Some(Splits::Kimahri));
+}
+
+#[test]
+fn undo_encounters_after_game_over() {
+    let mut run = Run::new(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    run.settings.undo_splits = true;
+    run.set(Pointer::EncounterCount, 3);
+    assert_eq!(run.win_battle(), Some(Splits::Kimahri));
+    assert_eq!(run.control.variables["encounters_last_split"], 3);
+    run.set(Pointer::EncounterCount, 2);
+    run.game_over(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    assert_eq!(run.control.variables["encounters_last_split"], 0);
+    assert_eq!(run.control.variables["encounters_this_split"], 2);
+}
+
+#[test]
+fn encounter_route() {
+    let path = std::env::temp_dir().join("ffx-autosplitter-encounter-route.txt");
+    std::fs::write(&path, "# Besaid\nKimahri = 5\n").unwrap();
+
+    let mut run = Run::new(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    run.settings.encounter_route.path = path.to_string_lossy().as_ref().into();
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.texts["encounter_delta"], "+0");
+    assert_eq!(run.control.texts["encounter_route"], "on route");
+
+    // the current split only counts once it has more encounters than planned
+    run.set(Pointer::EncounterCount, 3);
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.texts["encounter_delta"], "+0");
+    run.set(Pointer::EncounterCount, 6);
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.texts["encounter_delta"], "+1");
+    assert_eq!(run.control.texts["encounter_route"], "behind");
+
+    run.set(Pointer::EncounterCount, 3);
+    assert_eq!(run.win_battle(), Some(Splits::Kimahri));
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.texts["encounter_delta"], "-2");
+    assert_eq!(run.control.texts["encounter_route"], "ahead");
+
+    run.settings.encounter_route.path = "".into();
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.texts["encounter_delta"], "");
+    assert_eq!(run.control.texts["encounter_route"], "");
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn keep_level_split_after_game_over_at_the_same_story() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.settings.undo_splits = true;
+    run.set(Pointer::Level, Level::KILIKA_RESIDENTIAL_AREA);
+    assert_eq!(run.tick(), Some(Splits::Kilika));
+    run.game_over(Level::KILIKA_RESIDENTIAL_AREA, 330);
+    assert_eq!(run.control.split_index, Some(1));
+}
+
+#[test]
+fn keep_splits_without_game_over() {
+    let mut run = Run::new(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    run.settings.undo_splits = true;
+    assert_eq!(run.win_battle(), Some(Splits::Kimahri));
+    run.set(Pointer::Level, Level::NEW_GAME);
+    assert_eq!(run.tick(), None);
+    run.set(Pointer::Level, Level::BESAID_VILLAGE);
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.split_index, Some(1));
+}
+
+#[test]
+fn skip_level_splits_before_resuming() {
+    let behind = split_table::splits_behind(1000).collect::<Vec<_>>();
+    for split in [
+        Splits::BesaidVillage,
+        Splits::BesaidRoad,
+        Splits::Ixion,
+        Splits::EnterMoonflow,
+    ] {
+        assert!(behind.contains(&split), "{split:?} is behind");
+    }
+    assert!(behind.contains(&Splits::MacalaniaWoods) == false);
+
+    let mut run = Run::new(Level::DJOSE_FAYTH, 1000);
+    run.settings.set_split(Splits::Ixion, true);
+    // loading an older save does not split Ixion again
+    run.set(Pointer::Level, Level::NEW_GAME);
+    assert_eq!(run.tick(), None);
+    run.set(Pointer::Level, Level::DJOSE_FAYTH)
+        .set(Pointer::StoryProgression, Progress::IXION);
+    assert_eq!(run.tick(), None);
+    run.set(Pointer::Level, Level::DJOSE_OUTSIDE);
+    assert_eq!(run.tick(), None);
+}
+
+#[test]
+fn game_over() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.game_over(Level::KILIKA_WOODS, 330);
+    assert_eq!(run.control.variables.get("deaths"), Some(&1));
+}
+
+#[test]
+fn soft_reset_during_battle() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.set(Pointer::BattleState, BattleState::ONGOING);
+    assert_eq!(run.tick(), None);
+    run.set(Pointer::Level, Level::NEW_GAME);
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.variables.get("deaths"), None);
+}
+
+#[test]
+fn soft_reset_outside_of_battle() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.set(Pointer::Level, Level::NEW_GAME);
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.variables.get("deaths"), None);
+}
+
+#[test]
+fn soft_reset_after_escaping() {
+    let mut run = Run::new(Level::KILIKA_WOODS, 330);
+    run.set(Pointer::BattleState, BattleState::ONGOING);
+    assert_eq!(run.tick(), None);
+    run.set(
+        Pointer::BattleState,
+        BattleState::IS_OVER | BattleState::ESCAPED,
+    );
+    assert_eq!(run.tick(), None);
+    run.set(Pointer::Level, Level::NEW_GAME);
+    assert_eq!(run.tick(), None);
+    assert_eq!(run.control.variables.get("deaths"), None);
+}
+
+#[test]
+fn custom_split() {
+    let mut run = Run::new(Level::BESAID_VILLAGE, Progress::KIMAHRI);
+    run.settings.set_split(Splits::Kimahri, false);
+    run.settings.custom1 = CustomKind::BattleEnd;
+    run.settings.custom1_story = StoryChoice::Kimahri;
+    assert_eq!(run.win_battle(), Some(Splits::Custom1));
+}
+
+#[test]
+fn custom_split_from_any_level() {
+    let mut settings = Settings::defaults();
+    settings.custom1 = CustomKind::LevelTransition;
+    settings.custom1_to = LevelChoice::KilikaWoods;
+    // attaching in Kilika Woods is not a level transition
+    let mut run = Run::with(settings, Level::KILIKA_WOODS, 330);
+    run.set(Pointer::Level, Level::KILIKA_RESIDENTIAL_AREA);
+    assert_eq!(run.tick(), Some(Splits::Kilika));
+    run.set(Pointer::Level, Level::KILIKA_WOODS);
+    assert_eq!(run.tick(), Some(Splits::Custom1));
+}
+
+#[test]
+fn split_order_with_custom_split() {
+    let mut settings = Settings::defaults();
+    for split in Splits::iter() {
+        settings.set_split(split, split == Splits::Kilika);
+    }
+    settings.split_order = SplitOrder::Strict;
+    settings.custom1 = CustomKind::LevelTransition;
+    settings.custom1_to = LevelChoice::KilikaWoods;
+    let mut run = Run::with(settings, Level::KILIKA_RESIDENTIAL_AREA, 330);
+    run.set(Pointer::Level, Level::KILIKA_WOODS);
+    assert_eq!(run.tick(), Some(Splits::Custom1));
+    // the custom split moved LiveSplit past the segment of Kilika
+    run.set(Pointer::Level, Level::KILIKA_RESIDENTIAL_AREA);
+    assert_eq!(run.tick(), Some(Splits::Kilika));
+    assert_eq!(run.control.split_index, Some(2));
+}
+
+#[test]
+fn custom_split_rules() {
+    let rules = custom_splits::parse_rules(
+        "
+        # a comment
+        Custom1 = level 18 -> 46
+        custom2 = level any -> 46 story 330
+        Custom3 = battle 1540
+        Custom4 = battle 2530 map 65 formation 0/0 fanfare
+        Custom5 = story 2767 -> 2775
+        Custom5 = story
+        Kimahri = battle 214
+        ",
+    );
+    assert_eq!(
+        rules,
+        [
+            (Splits::Custom1, level(18, 46)),
+            (
+                Splits::Custom2,
+                split_table::Trigger::Level {
+                    from: None,
+                    to: Some(46),
+                    story: Some(330),
+                },
+            ),
+            (Splits::Custom3, battle(1540)),
+            (Splits::Custom4, battle(2530).at(65, 0, 0).fanfare()),
+            (Splits::Custom5, advance(2767).to(2775)),
+        ]
+    );
+}
+
+#[test]
+fn lagoon_during_cutscene() {
+    let mut run = Run::new(Level::LAGOON, Progress::LAGOON);
+    run.set(Pointer::CutsceneType, 73);
+    assert_eq!(run.tick(), None);
+    run.set(Pointer::StoryProgression, 124);
+    assert_eq!(run.tick(), Some(Splits::Lagoon));
+}
+
+#[test]
+fn lagoon_outside_of_cutscene() {
+    let mut run = Run::new(Level::LAGOON, Progress::LAGOON);
+    run.set(Pointer::StoryProgression, 124);
+    assert_eq!(run.tick(), None);
+}
+
+#[test]
+fn mrr_during_cutscene() {
+    let mut run = Run::new(Level::MUSHROOM_ROCK_ROAD, Progress::MRR);
+    run.set(Pointer::CutsceneType, 940)
+        .set(Pointer::StoryProgression, 840);
+    assert_eq!(run.tick(), Some(Splits::Mrr));
+}
+
+#[test]
+fn mrr_outside_of_cutscene() {
+    let mut run = Run::new(Level::MUSHROOM_ROCK_ROAD, Progress::MRR);
+    run.set(Pointer::CutsceneType, 12)
+        .set(Pointer::StoryProgression, 840);
+    assert_eq!(run.tick(), None);
+}
+
+#[test]
+fn lss_segment_names() {
+    let lss = "
+        <Segments>
+          <Segment><Name>-Kimahri</Name></Segment>
+          <Segment><Name>{Besaid}Sinspawn Echuilles</Name></Segment>
+          <Segment><Name><![CDATA[MRR Skip]]></Name></Segment>
+          <Segment><Name>Yu Yevon</Name></Segment>
+        </Segments>
+    ";
+    let names = lss::segment_names(lss).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["Kimahri", "Sinspawn Echuilles", "MRR Skip", "Yu Yevon"]
+    );
+}
+
+#[test]
+fn lss_find_split() {
+    for (segment, split) in [
+        ("Kimahri", Some(Splits::Kimahri)),
+        ("kimahri", Some(Splits::Kimahri)),
+        ("Sinspawn Ammes", Some(Splits::Ammes)),
+        ("Kilika Woods", Some(Splits::Kilika)),
+        ("MRR Skip", Some(Splits::MrrSkip)),
+        ("Mushroom Rock Road", Some(Splits::Mrr)),
+        ("Seymour Natus", Some(Splits::Natus)),
+        ("Lunch break", None),
+    ] {
+        assert_eq!(lss::find_split(segment), split, "segment {segment}");
+    }
+}
+
+/// The splits that are enabled after choosing the preset.
+fn preset(preset: Preset) -> Vec<Splits> {
+    let mut settings = Settings::defaults();
+    for split in Splits::iter() {
+        if let Some(enabled) = preset.enables(split) {
+            settings.set_split(split, enabled);
+        }
+    }
+    return Splits::iter()
+        .filter(|split| settings.filter(*split))
+        .collect();
+}
+
+#[test]
+fn presets() {
+    let any_percent = preset(Preset::AnyPercent);
+    assert_eq!(preset(Preset::Manual), any_percent, "defaults are Any%");
+    assert!(any_percent.contains(&Splits::Kilika));
+    assert!(any_percent.contains(&Splits::Mrr));
+    assert!(any_percent.contains(&Splits::Gui));
+    assert!(any_percent.contains(&Splits::MrrSkip) == false);
+
+    let mrr_skip = preset(Preset::AnyPercentMrrSkip);
+    let mut expected = any_percent.clone();
+    expected.retain(|split| ![Splits::Mrr, Splits::Gui].contains(split));
+    expected.push(Splits::MrrSkip);
+    expected.sort_by_key(|split| Splits::iter().position(|s| s == *split));
+    assert_eq!(mrr_skip, expected);
+
+    let all = preset(Preset::AllSplits);
+    let with_setting = Splits::iter()
+        .filter(|split| split.setting_key().is_some())
+        .collect::<Vec<_>>();
+    assert_eq!(all, with_setting);
+
+    let bosses = preset(Preset::Bosses);
+    assert!(bosses.contains(&Splits::Kimahri));
+    assert!(bosses.contains(&Splits::Garuda));
+    assert!(bosses.contains(&Splits::Kilika) == false);
+    assert!(bosses.contains(&Splits::Mrr) == false);
+    assert!(bosses.iter().all(|split| {
+        split.triggers().iter().any(|trigger| {
+            matches!(
+                trigger,
+                split_table::Trigger::Battle { .. } | split_table::Trigger::HpZero { .. }
+            )
+        })
+    }));
+}
+
+/// A hand-written trace, not recorded from the game: start on the title
+/// screen, remove a load, split when leaving Kilika Woods, reset.
+const SYNTHETIC_KILIKA_SPLIT: &str =
+    include_str!("../fixtures/synthetic-start-kilika-split-reset.log");
+
+#[test]
+fn replay_synthetic_trace() {
+    let trace = SYNTHETIC_KILIKA_SPLIT.parse::<Trace>().unwrap();
+    let mut settings = Settings::defaults();
+    settings.set_option("reset", true);
+
+    let events = replay::replay(&settings, &trace);
+    let expected = [
+        (10, EventKind::Start),
+        (20, EventKind::PauseGameTime),
+        (30, EventKind::ResumeGameTime),
+        (40, EventKind::Split(Splits::Kilika)),
+        (55, EventKind::Reset),
+    ]
+    .map(|(tick, kind)| Event { tick, kind });
+    assert_eq!(events, expected);
+}
+
+#[test]
+fn replay_synthetic_trace_as_written() {
+    let trace = SYNTHETIC_KILIKA_SPLIT.parse::<Trace>().unwrap();
+    let recorded = trace.sections[0]
+        .frames
+        .iter()
+        .filter_map(|frame| Some((u64::from(frame.tick), frame.action?)))
+        .collect::<Vec<_>>();
+
+    let mut settings = Settings::defaults();
+    settings.set_option("reset", true);
+    let replayed = replay::replay(&settings, &trace)
+        .into_iter()
+        .filter_map(|event| {
+            let action = match event.kind {
+                EventKind::Start => RecordedAction::Start,
+                EventKind::Split(split) => RecordedAction::Split(split),
+                EventKind::Reset => RecordedAction::Reset,
+                _ => return None,
+            };
+            return Some((event.tick, action));
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(replayed, recorded);
+}
@@ -0,0 +1,89 @@
+use asr::{
+    time::Duration,
+    timer::{self, TimerState},
+};
+
+/// The parts of the LiveSplit timer that the splitter reads and controls.
+///
+/// All timer interaction goes through this trait, so the splitter logic
+/// can drive a simulated timer, e.g. when replaying a trace.
+pub trait TimerControl {
+    fn state(&self) -> TimerState;
+    /// The index of the segment the timer is on, `None` if it isn't running.
+    fn current_split_index(&self) -> Option<u64>;
+    fn start(&mut self);
+    fn split(&mut self);
+    fn skip_split(&mut self);
+    fn undo_split(&mut self);
+    fn reset(&mut self);
+    fn pause_game_time(&mut self);
+    fn resume_game_time(&mut self);
+    fn set_game_time(&mut self, time: Duration);
+    /// The real time of the run so far, `None` if it isn't running.
+    fn real_time(&self) -> Option<Duration>;
+    /// The game time of the run so far, `None` if it isn't running.
+    fn game_time(&self) -> Option<Duration>;
+    fn set_variable_int(&mut self, key: &str, value: u32);
+    fn set_variable(&mut self, key: &str, value: &str);
+}
+
+/// The actual LiveSplit timer.
+pub(crate) struct AsrTimer;
+
+impl TimerControl for AsrTimer {
+    fn state(&self) -> TimerState {
+        return timer::state();
+    }
+
+    fn current_split_index(&self) -> Option<u64> {
+        return timer::current_split_index();
+    }
+
+    fn start(&mut self) {
+        timer::start();
+    }
+
+    fn split(&mut self) {
+        timer::split();
+    }
+
+    fn skip_split(&mut self) {
+        timer::skip_split();
+    }
+
+    fn undo_split(&mut self) {
+        timer::undo_split();
+    }
+
+    fn reset(&mut self) {
+        timer::reset();
+    }
+
+    fn pause_game_time(&mut self) {
+        timer::pause_game_time();
+    }
+
+    fn resume_game_time(&mut self) {
+        timer::resume_game_time();
+    }
+
+    fn set_game_time(&mut self, time: Duration) {
+        timer::set_game_time(time);
+    }
+
+    fn real_time(&self) -> Option<Duration> {
+        return timer::current_time();
+    }
+
+    fn game_time(&self) -> Option<Duration> {
+        return timer::current_game_time();
+    }
+
+    fn set_variable_int(&mut self, key: &str, value: u32) {
+        timer::set_variable_int(key, value);
+    }
+
+    fn set_variable(&mut self, key: &str, value: &str) {
+        timer::set_variable(key, value);
+    }
+}
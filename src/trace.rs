@@ -0,0 +1,116 @@
+//! Per-tick trace of every game value the splitter reads.
+//!
+//! A trace is written to the log, one line per tick in which anything changed:
+//!
+//! ```text
+//! [FFX]: TRACE-BEGIN 1
+//! [FFX]: TRACE 0 0=0 1=c 2=17 3=0 ... timer=N
+//! [FFX]: TRACE 412 2=14 3=f action=split:Lagoon
+//! ```
+//!
+//! - `TRACE-BEGIN <version>` starts a new trace, e.g. after attaching to the game.
+//! - `TRACE <tick>` is the number of ticks since the trace started.
+//! - `<pointer>=<hex>` is the new raw value of a [`Pointer`], by its index.
+//!   `<pointer>=-` means the value could not be read.
+//! - `timer=<state>` is the timer state, if it changed:
+//!   `N` (not running), `R` (running), `P` (paused), `E` (ended) or `?`.
+//! - `action=<action>` is the action the splitter took in that tick, if any:
+//!   `start`, `start-paused`, `reset`, `restart` or `split:<split>`.
+//!
+//! Values that did not change since the previous line are not repeated.
+//! While recording, every value is read once per tick and the splitter runs on
+//! those same values, so a replay of the trace sees what the splitter saw.
+
+use asr::{arrayvec::ArrayString, timer::TimerState};
+use core::fmt::Write as _;
+use strum::IntoEnumIterator as _;
+
+use crate::{
+    Action,
+    game_memory::{FakeMemory, Pointer},
+    log,
+};
+
+pub const VERSION: u32 = 1;
+
+pub struct Recorder {
+    tick: u32,
+    last: Option<FakeMemory>,
+    timer: char,
+}
+
+impl Recorder {
+    pub const fn new() -> Self {
+        Self {
+            tick: 0,
+            last: None,
+            timer: ' ',
+        }
+    }
+
+    /// Start a new trace with the next recorded tick.
+    pub fn restart(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Record the values that the splitter used in this tick.
+    pub fn record(&mut self, values: &FakeMemory, timer: TimerState, action: Action) {
+        let last = match &mut self.last {
+            Some(last) => last,
+            None => {
+                log!("TRACE-BEGIN {}", VERSION);
+                self.last.insert(FakeMemory::new())
+            }
+        };
+
+        let mut line = ArrayString::<1024>::new();
+
+        for pointer in Pointer::iter() {
+            let value = values.get(pointer);
+            if value == last.get(pointer) {
+                continue;
+            }
+            let _ = match value {
+                Some(value) => {
+                    last.set(pointer, value);
+                    write!(line, " {}={:x}", pointer.index(), value)
+                }
+                None => {
+                    last.unset(pointer);
+                    write!(line, " {}=-", pointer.index())
+                }
+            };
+        }
+
+        let timer = timer_code(timer);
+        if timer != self.timer {
+            self.timer = timer;
+            let _ = write!(line, " timer={}", timer);
+        }
+
+        let _ = match action {
+            Action::Nothing => Ok(()),
+            Action::Start => write!(line, " action=start"),
+            Action::StartPaused => write!(line, " action=start-paused"),
+            Action::Reset => write!(line, " action=reset"),
+            Action::Restart => write!(line, " action=restart"),
+            Action::Split(split) => write!(line, " action=split:{:?}", split),
+        };
+
+        if line.is_empty() == false {
+            log!("TRACE {}{}", self.tick, line);
+        }
+
+        self.tick = self.tick.wrapping_add(1);
+    }
+}
+
+fn timer_code(state: TimerState) -> char {
+    return match state {
+        TimerState::NotRunning => 'N',
+        TimerState::Running => 'R',
+        TimerState::Paused => 'P',
+        TimerState::Ended => 'E',
+        _ => '?',
+    };
+}
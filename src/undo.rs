@@ -0,0 +1,93 @@
+//! Undoing splits when a save from before them is loaded.
+//!
+//! A game over returns to the title screen. When a save is loaded from there,
+//! the story progression goes back to that of the save. Every split that
+//! happened at a later story progression is undone, so that it can split again
+//! on the retry. Battles and cutscenes happen without the story progressing,
+//! so their splits are also undone when the save has the same progression.
+//! Quitting to the title screen on purpose never undoes anything.
+
+use asr::arrayvec::ArrayVec;
+
+use crate::{
+    GameMemory, Level, Progress, Read, SeenSplits, Settings, Splits, log,
+    timer_control::TimerControl,
+};
+
+/// The recent splits, with the story progression at which they happened,
+/// and whether a save at that same progression is from before them.
+pub struct Undo {
+    splits: ArrayVec<(Splits, Progress, bool), 16>,
+    story: Progress,
+    on_title_screen: bool,
+}
+
+impl Undo {
+    pub const fn new() -> Self {
+        Self {
+            splits: ArrayVec::new_const(),
+            story: Progress(0),
+            on_title_screen: false,
+        }
+    }
+
+    /// Remember the split, at the story progression of the current tick.
+    pub fn record(&mut self, split: Splits, keeps_story: bool) {
+        if self.splits.is_full() {
+            self.splits.remove(0);
+        }
+        self.splits.push((split, self.story, keeps_story));
+    }
+
+    /// Returns the splits that were undone, the last one first.
+    pub fn update(
+        &mut self,
+        settings: &Settings,
+        game_over: bool,
+        read: &mut Read<'_, impl GameMemory>,
+        seen: &mut SeenSplits,
+        control: &mut impl TimerControl,
+    ) -> ArrayVec<Splits, 16> {
+        let mut undone = ArrayVec::new();
+        let story = read.story_progression().current;
+        self.story = story;
+
+        if settings.undo_splits == false {
+            self.on_title_screen = false;
+            return undone;
+        }
+
+        if game_over {
+            self.on_title_screen = true;
+            return undone;
+        }
+        let level = read.level().current;
+        if level.new_game() {
+            return undone;
+        }
+        if self.on_title_screen == false
+            || Level::in_game(level.0) == false
+            || read.loading().is_loading()
+        {
+            return undone;
+        }
+        self.on_title_screen = false;
+
+        while let Some(&(split, at, keeps_story)) = self.splits.last() {
+            if story > at || (story == at && keeps_story == false) {
+                break;
+            }
+            log!(
+                "Undo split {:?} from story {:?}, the loaded save is at {:?}",
+                split,
+                at,
+                story
+            );
+            control.undo_split();
+            seen.remove(&split);
+            self.splits.pop();
+            undone.push(split);
+        }
+        return undone;
+    }
+}